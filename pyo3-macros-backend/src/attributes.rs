@@ -11,11 +11,14 @@ use crate::deprecations::{Deprecation, Deprecations};
 pub mod kw {
     syn::custom_keyword!(annotation);
     syn::custom_keyword!(attribute);
+    syn::custom_keyword!(default);
+    syn::custom_keyword!(from_item_all);
     syn::custom_keyword!(from_py_with);
     syn::custom_keyword!(get);
     syn::custom_keyword!(item);
     syn::custom_keyword!(pass_module);
     syn::custom_keyword!(name);
+    syn::custom_keyword!(rename_all);
     syn::custom_keyword!(set);
     syn::custom_keyword!(signature);
     syn::custom_keyword!(text_signature);
@@ -0,0 +1,78 @@
+//! Implementation of `#[derive(Element)]`.
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, parse_quote, spanned::Spanned, DeriveInput, Meta, NestedMeta, Result};
+
+/// Checks that the struct is `#[repr(C)]` and not packed, so that its fields are laid out as
+/// computed by `ElementLayout::repr_c`.
+fn check_repr(tokens: &DeriveInput) -> Result<()> {
+    let mut repr_c = false;
+    for attr in tokens
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+    {
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in &list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("C") => repr_c = true,
+                    NestedMeta::Meta(meta) if meta.path().is_ident("packed") => bail_spanned!(
+                        meta.span() => "#[derive(Element)] does not support packed structs"
+                    ),
+                    _ => {}
+                }
+            }
+        }
+    }
+    ensure_spanned!(
+        repr_c,
+        tokens.ident.span() => "#[derive(Element)] requires the struct to be #[repr(C)]"
+    );
+    Ok(())
+}
+
+pub fn build_derive_element(tokens: &DeriveInput) -> Result<TokenStream> {
+    let fields = match &tokens.data {
+        syn::Data::Struct(st) => &st.fields,
+        syn::Data::Enum(_) | syn::Data::Union(_) => bail_spanned!(
+            tokens.span() => "#[derive(Element)] is only supported for structs"
+        ),
+    };
+    check_repr(tokens)?;
+
+    let mut generics = tokens.generics.clone();
+    for param in tokens.generics.type_params() {
+        let ident = &param.ident;
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ident: pyo3::buffer::Element));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_layouts = fields.iter().enumerate().map(|(index, field)| {
+        let ty = &field.ty;
+        let name = field
+            .ident
+            .as_ref()
+            .map_or_else(|| index.to_string(), |ident| ident.unraw().to_string());
+        quote! {
+            (
+                #name,
+                <#ty as pyo3::buffer::Element>::layout()?,
+                ::std::mem::align_of::<#ty>(),
+            )
+        }
+    });
+    let ident = &tokens.ident;
+    Ok(quote! {
+        unsafe impl #impl_generics pyo3::buffer::Element for #ident #ty_generics #where_clause {
+            fn layout() -> ::std::option::Option<pyo3::buffer::ElementLayout> {
+                ::std::option::Option::Some(pyo3::buffer::ElementLayout::repr_c(
+                    ::std::mem::size_of::<Self>(),
+                    ::std::vec![#(#field_layouts),*],
+                ))
+            }
+        }
+    })
+}
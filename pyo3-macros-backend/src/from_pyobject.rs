@@ -1,6 +1,8 @@
 use crate::attributes::{self, get_pyo3_options, FromPyWithAttribute};
+use crate::utils::option_type_argument;
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::{
     parenthesized,
     parse::{Parse, ParseStream},
@@ -61,10 +63,7 @@ impl<'a> Enum<'a> {
 
                 match maybe_ret {
                     ok @ Ok(_) => return ok,
-                    Err(inner) => {
-                        let py = pyo3::PyNativeType::py(obj);
-                        err_reasons.push_str(&format!("{}\n", inner.instance(py).str().unwrap()));
-                    }
+                    Err(inner) => variant_errors.push(inner),
                 }
             );
 
@@ -81,13 +80,10 @@ impl<'a> Enum<'a> {
         };
         let ty_name = self.enum_ident.to_string();
         quote!(
-            let mut err_reasons = String::new();
+            let mut variant_errors = ::std::vec::Vec::new();
             #(#var_extracts)*
-            let err_msg = format!("failed to extract enum {} ('{}')\n{}",
-                #ty_name,
-                #error_names,
-                &err_reasons);
-            Err(pyo3::exceptions::PyTypeError::new_err(err_msg))
+            let py = pyo3::PyNativeType::py(obj);
+            Err(pyo3::ExtractionError::new_enum_err(py, #ty_name, #error_names, variant_errors))
         )
     }
 }
@@ -99,8 +95,8 @@ impl<'a> Enum<'a> {
 enum ContainerType<'a> {
     /// Struct Container, e.g. `struct Foo { a: String }`
     ///
-    /// Variant contains the list of field identifiers and the corresponding extraction call.
-    Struct(Vec<(&'a Ident, FieldPyO3Attributes)>),
+    /// Variant contains the list of fields and how each is extracted.
+    Struct(Vec<StructField<'a>>),
     /// Newtype struct container, e.g. `#[transparent] struct Foo { a: String }`
     ///
     /// The field specified by the identifier is extracted directly from the object.
@@ -164,18 +160,13 @@ impl<'a> Container<'a> {
                     .expect("Named fields should have identifiers");
                 ContainerType::StructNewtype(ident)
             }
-            (Fields::Named(named), false) => {
-                let mut fields = Vec::new();
-                for field in named.named.iter() {
-                    let ident = field
-                        .ident
-                        .as_ref()
-                        .expect("Named fields should have identifiers");
-                    let attrs = FieldPyO3Attributes::from_attrs(&field.attrs)?;
-                    fields.push((ident, attrs))
-                }
-                ContainerType::Struct(fields)
-            }
+            (Fields::Named(named), false) => ContainerType::Struct(
+                named
+                    .named
+                    .iter()
+                    .map(|field| StructField::new(field, &options))
+                    .collect::<Result<_>>()?,
+            ),
             (Fields::Unit, _) => unreachable!(), // covered by length check above
         };
         let err_name = options.annotation.map_or_else(
@@ -192,6 +183,16 @@ impl<'a> Container<'a> {
         Ok(v)
     }
 
+    /// The name of the container used in error messages, e.g. `Foo` or `Foo::Variant`.
+    fn type_name(&self) -> String {
+        self.path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
     /// Build derivation body for a struct.
     fn build(&self) -> TokenStream {
         match &self.ty {
@@ -205,17 +206,12 @@ impl<'a> Container<'a> {
     fn build_newtype_struct(&self, field_ident: Option<&Ident>) -> TokenStream {
         let self_ty = &self.path;
         if let Some(ident) = field_ident {
-            let error_msg = format!(
-                "failed to extract field {}.{}",
-                quote!(#self_ty),
-                quote!(#ident)
-            );
+            let type_name = self.type_name();
+            let field_name = ident.unraw().to_string();
             quote!(
                 Ok(#self_ty{#ident: obj.extract().map_err(|inner| {
                     let py = pyo3::PyNativeType::py(obj);
-                    let new_err = pyo3::exceptions::PyTypeError::new_err(#error_msg);
-                    new_err.set_cause(py, Some(inner));
-                    new_err
+                    pyo3::ExtractionError::new_field_err(py, #type_name, #field_name, inner)
                 })?})
             )
         } else {
@@ -225,9 +221,13 @@ impl<'a> Container<'a> {
             } else {
                 format!("failed to extract inner field of {}", quote!(#self_ty))
             };
+            let type_name = self.type_name();
             quote!(
                 Ok(#self_ty(obj.extract().map_err(|inner| {
                     let py = pyo3::PyNativeType::py(obj);
+                    if inner.is_instance::<pyo3::ExtractionError>(py) {
+                        return pyo3::ExtractionError::with_type_name(py, #type_name, inner);
+                    }
                     let err_msg = format!("{}: {}",
                         #error_msg,
                         inner.instance(py).str().unwrap());
@@ -240,14 +240,13 @@ impl<'a> Container<'a> {
     fn build_tuple_struct(&self, len: usize) -> TokenStream {
         let self_ty = &self.path;
         let mut fields: Punctuated<TokenStream, syn::Token![,]> = Punctuated::new();
+        let type_name = self.type_name();
         for i in 0..len {
-            let error_msg = format!("failed to extract field {}.{}", quote!(#self_ty), i);
+            let field_name = i.to_string();
             fields.push(quote!(
                 s.get_item(#i).extract().map_err(|inner| {
-                let py = pyo3::PyNativeType::py(obj);
-                let new_err = pyo3::exceptions::PyTypeError::new_err(#error_msg);
-                new_err.set_cause(py, Some(inner));
-                new_err
+                    let py = pyo3::PyNativeType::py(obj);
+                    pyo3::ExtractionError::new_field_err(py, #type_name, #field_name, inner)
                 })?));
         }
         let msg = if self.is_enum_variant {
@@ -268,36 +267,57 @@ impl<'a> Container<'a> {
         )
     }
 
-    fn build_struct(&self, tups: &[(&Ident, FieldPyO3Attributes)]) -> TokenStream {
+    fn build_struct(&self, struct_fields: &[StructField]) -> TokenStream {
         let self_ty = &self.path;
+        let type_name = self.type_name();
         let mut fields: Punctuated<TokenStream, syn::Token![,]> = Punctuated::new();
-        for (ident, attrs) in tups {
-            let getter = match &attrs.getter {
-                FieldGetter::GetAttr(Some(name)) => quote!(getattr(#name)),
-                FieldGetter::GetAttr(None) => quote!(getattr(stringify!(#ident))),
-                FieldGetter::GetItem(Some(key)) => quote!(get_item(#key)),
-                FieldGetter::GetItem(None) => quote!(get_item(stringify!(#ident))),
+        for field in struct_fields {
+            let ident = field.ident;
+            let (getter, missing_err) = match &field.access {
+                FieldAccess::Attribute(name) => (
+                    quote!(getattr(#name)),
+                    quote!(pyo3::exceptions::PyAttributeError),
+                ),
+                FieldAccess::Item(key) => (
+                    quote!(get_item(#key)),
+                    quote!(pyo3::exceptions::PyLookupError),
+                ),
             };
-            let conversion_error_msg =
-                format!("failed to extract field {}.{}", quote!(#self_ty), ident);
-            let get_field = quote!(obj.#getter?);
-            let extractor = match &attrs.from_py_with {
+            let field_name = ident.unraw().to_string();
+            let extract = |value: TokenStream| match &field.from_py_with {
                 None => quote!(
-                    #get_field.extract().map_err(|inner| {
+                    #value.extract().map_err(|inner| {
                     let py = pyo3::PyNativeType::py(obj);
-                    let new_err = pyo3::exceptions::PyTypeError::new_err(#conversion_error_msg);
-                    new_err.set_cause(py, Some(inner));
-                    new_err
+                    pyo3::ExtractionError::new_field_err(py, #type_name, #field_name, inner)
                 })?),
                 Some(FromPyWithAttribute(expr_path)) => quote! (
-                    #expr_path(#get_field).map_err(|inner| {
+                    #expr_path(#value).map_err(|inner| {
                         let py = pyo3::PyNativeType::py(obj);
-                        let new_err = pyo3::exceptions::PyTypeError::new_err(#conversion_error_msg);
-                        new_err.set_cause(py, Some(inner));
-                        new_err
+                        pyo3::ExtractionError::new_field_err(py, #type_name, #field_name, inner)
                     })?
                 ),
             };
+            let fallback = match &field.default {
+                Some(FieldDefault::Default) => Some(quote!(::std::default::Default::default())),
+                Some(FieldDefault::Expr(expr)) => Some(quote!(#expr)),
+                None if field.is_option => Some(quote!(::std::option::Option::None)),
+                None => None,
+            };
+            let extractor = match fallback {
+                None => extract(quote!(obj.#getter?)),
+                Some(fallback) => {
+                    let extract_value = extract(quote!(value));
+                    quote!(
+                        match obj.#getter {
+                            Ok(value) => #extract_value,
+                            Err(err) if err.is_instance::<#missing_err>(pyo3::PyNativeType::py(obj)) => {
+                                #fallback
+                            }
+                            Err(err) => return Err(err),
+                        }
+                    )
+                }
+            };
 
             fields.push(quote!(#ident: #extractor));
         }
@@ -305,11 +325,15 @@ impl<'a> Container<'a> {
     }
 }
 
-struct ContainerOptions {
+pub(crate) struct ContainerOptions {
     /// Treat the Container as a Wrapper, directly extract its fields from the input object.
-    transparent: bool,
+    pub transparent: bool,
     /// Change the name of an enum variant in the generated error message.
-    annotation: Option<syn::LitStr>,
+    pub annotation: Option<syn::LitStr>,
+    /// Extract fields from items instead of attributes unless specified otherwise.
+    pub from_item_all: bool,
+    /// Change the case convention of field names used as attribute names and item keys.
+    pub rename_all: Option<RenameRule>,
 }
 
 /// Attributes for deriving FromPyObject scoped on containers.
@@ -319,6 +343,10 @@ enum ContainerPyO3Attribute {
     Transparent(attributes::kw::transparent),
     /// Change the name of an enum variant in the generated error message.
     ErrorAnnotation(LitStr),
+    /// Extract fields from items instead of attributes unless specified otherwise.
+    FromItemAll(attributes::kw::from_item_all),
+    /// Change the case convention of field names used as attribute names and item keys.
+    RenameAll(LitStr, RenameRule),
 }
 
 impl Parse for ContainerPyO3Attribute {
@@ -331,6 +359,14 @@ impl Parse for ContainerPyO3Attribute {
             let _: attributes::kw::annotation = input.parse()?;
             let _: Token![=] = input.parse()?;
             input.parse().map(ContainerPyO3Attribute::ErrorAnnotation)
+        } else if lookahead.peek(attributes::kw::from_item_all) {
+            input.parse().map(ContainerPyO3Attribute::FromItemAll)
+        } else if lookahead.peek(attributes::kw::rename_all) {
+            let _: attributes::kw::rename_all = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            let lit: LitStr = input.parse()?;
+            let rule = RenameRule::from_lit(&lit)?;
+            Ok(ContainerPyO3Attribute::RenameAll(lit, rule))
         } else {
             Err(lookahead.error())
         }
@@ -338,10 +374,12 @@ impl Parse for ContainerPyO3Attribute {
 }
 
 impl ContainerOptions {
-    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
         let mut options = ContainerOptions {
             transparent: false,
             annotation: None,
+            from_item_all: false,
+            rename_all: None,
         };
         for attr in attrs {
             if let Some(pyo3_attrs) = get_pyo3_options(attr)? {
@@ -361,6 +399,20 @@ impl ContainerOptions {
                             );
                             options.annotation = Some(lit_str);
                         }
+                        ContainerPyO3Attribute::FromItemAll(kw) => {
+                            ensure_spanned!(
+                                !options.from_item_all,
+                                kw.span() => "`from_item_all` may only be provided once"
+                            );
+                            options.from_item_all = true;
+                        }
+                        ContainerPyO3Attribute::RenameAll(lit_str, rule) => {
+                            ensure_spanned!(
+                                options.rename_all.is_none(),
+                                lit_str.span() => "`rename_all` may only be provided once"
+                            );
+                            options.rename_all = Some(rule);
+                        }
                     }
                 }
             }
@@ -369,22 +421,91 @@ impl ContainerOptions {
     }
 }
 
+/// Case conventions accepted by `#[pyo3(rename_all = "...")]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RenameRule {
+    Lowercase,
+    Uppercase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    fn from_lit(lit: &LitStr) -> Result<Self> {
+        Ok(match lit.value().as_str() {
+            "lowercase" => RenameRule::Lowercase,
+            "UPPERCASE" => RenameRule::Uppercase,
+            "PascalCase" => RenameRule::PascalCase,
+            "camelCase" => RenameRule::CamelCase,
+            "snake_case" => RenameRule::SnakeCase,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnakeCase,
+            "kebab-case" => RenameRule::KebabCase,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebabCase,
+            _ => bail_spanned!(
+                lit.span() => "expected one of \"lowercase\", \"UPPERCASE\", \"PascalCase\", \
+                \"camelCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\" or \
+                \"SCREAMING-KEBAB-CASE\""
+            ),
+        })
+    }
+
+    /// Applies the rule to a `snake_case` Rust field name.
+    fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::Lowercase | RenameRule::SnakeCase => field.to_owned(),
+            RenameRule::Uppercase | RenameRule::ScreamingSnakeCase => field.to_ascii_uppercase(),
+            RenameRule::KebabCase => field.replace('_', "-"),
+            RenameRule::ScreamingKebabCase => field.replace('_', "-").to_ascii_uppercase(),
+            RenameRule::PascalCase | RenameRule::CamelCase => {
+                let mut renamed = String::with_capacity(field.len());
+                let mut capitalize = self == RenameRule::PascalCase;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = !renamed.is_empty() || self == RenameRule::PascalCase;
+                    } else if capitalize {
+                        renamed.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        renamed.push(c);
+                    }
+                }
+                renamed
+            }
+        }
+    }
+}
+
 /// Attributes for deriving FromPyObject scoped on fields.
 #[derive(Clone, Debug)]
-struct FieldPyO3Attributes {
-    getter: FieldGetter,
-    from_py_with: Option<FromPyWithAttribute>,
+pub(crate) struct FieldPyO3Attributes {
+    pub getter: Option<FieldGetter>,
+    pub from_py_with: Option<FromPyWithAttribute>,
+    pub default: Option<FieldDefault>,
 }
 
 #[derive(Clone, Debug)]
-enum FieldGetter {
+pub(crate) enum FieldGetter {
     GetItem(Option<syn::Lit>),
     GetAttr(Option<LitStr>),
 }
 
+/// The value used for a field when its attribute or item is missing.
+#[derive(Clone, Debug)]
+pub(crate) enum FieldDefault {
+    /// `#[pyo3(default)]`, using `Default::default()`.
+    Default,
+    /// `#[pyo3(default = "expr")]`
+    Expr(syn::Expr),
+}
+
 enum FieldPyO3Attribute {
     Getter(FieldGetter),
     FromPyWith(FromPyWithAttribute),
+    Default(FieldDefault),
 }
 
 impl Parse for FieldPyO3Attribute {
@@ -428,6 +549,16 @@ impl Parse for FieldPyO3Attribute {
             }
         } else if lookahead.peek(attributes::kw::from_py_with) {
             input.parse().map(FieldPyO3Attribute::FromPyWith)
+        } else if lookahead.peek(attributes::kw::default) {
+            let _: attributes::kw::default = input.parse()?;
+            if input.peek(Token![=]) {
+                let _: Token![=] = input.parse()?;
+                let expr: LitStr = input.parse()?;
+                expr.parse()
+                    .map(|expr| FieldPyO3Attribute::Default(FieldDefault::Expr(expr)))
+            } else {
+                Ok(FieldPyO3Attribute::Default(FieldDefault::Default))
+            }
         } else {
             Err(lookahead.error())
         }
@@ -437,9 +568,10 @@ impl Parse for FieldPyO3Attribute {
 impl FieldPyO3Attributes {
     /// Extract the field attributes.
     ///
-    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
         let mut getter = None;
         let mut from_py_with = None;
+        let mut default = None;
 
         for attr in attrs {
             if let Some(pyo3_attrs) = get_pyo3_options(attr)? {
@@ -459,14 +591,82 @@ impl FieldPyO3Attributes {
                             );
                             from_py_with = Some(from_py_with_attr);
                         }
+                        FieldPyO3Attribute::Default(field_default) => {
+                            ensure_spanned!(
+                                default.is_none(),
+                                attr.span() => "`default` may only be provided once"
+                            );
+                            default = Some(field_default);
+                        }
                     }
                 }
             }
         }
 
         Ok(FieldPyO3Attributes {
-            getter: getter.unwrap_or(FieldGetter::GetAttr(None)),
+            getter,
             from_py_with,
+            default,
+        })
+    }
+}
+
+/// How a named field is looked up on the Python object.
+#[derive(Clone, Debug)]
+pub(crate) enum FieldAccess {
+    /// `obj.getattr(name)`
+    Attribute(LitStr),
+    /// `obj.get_item(key)`
+    Item(syn::Lit),
+}
+
+/// A named field of a struct container, with its attributes resolved against the container
+/// options.
+#[derive(Debug)]
+pub(crate) struct StructField<'a> {
+    pub ident: &'a Ident,
+    pub access: FieldAccess,
+    pub from_py_with: Option<FromPyWithAttribute>,
+    pub default: Option<FieldDefault>,
+    /// Whether the field is an `Option<T>`, which is `None` when missing.
+    pub is_option: bool,
+}
+
+impl<'a> StructField<'a> {
+    pub fn new(field: &'a syn::Field, options: &ContainerOptions) -> Result<Self> {
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("Named fields should have identifiers");
+        let attrs = FieldPyO3Attributes::from_attrs(&field.attrs)?;
+        let default_name = || {
+            let name = ident.unraw().to_string();
+            let name = match options.rename_all {
+                Some(rule) => rule.apply(&name),
+                None => name,
+            };
+            LitStr::new(&name, ident.span())
+        };
+        let access = match attrs.getter {
+            Some(FieldGetter::GetAttr(Some(name))) => FieldAccess::Attribute(name),
+            Some(FieldGetter::GetItem(Some(key))) => FieldAccess::Item(key),
+            Some(FieldGetter::GetAttr(None)) => FieldAccess::Attribute(default_name()),
+            Some(FieldGetter::GetItem(None)) => {
+                ensure_spanned!(
+                    !options.from_item_all,
+                    ident.span() => "useless `item` - the struct is already annotated with `from_item_all`"
+                );
+                FieldAccess::Item(default_name().into())
+            }
+            None if options.from_item_all => FieldAccess::Item(default_name().into()),
+            None => FieldAccess::Attribute(default_name()),
+        };
+        Ok(StructField {
+            ident,
+            access,
+            from_py_with: attrs.from_py_with,
+            default: attrs.default,
+            is_option: option_type_argument(&field.ty).is_some(),
         })
     }
 }
@@ -0,0 +1,269 @@
+use crate::from_pyobject::{ContainerOptions, FieldAccess, StructField};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, spanned::Spanned, DataEnum, DeriveInput, Fields, Ident, Result};
+
+/// The conversion trait being derived.
+///
+/// Both derives share the container and field attributes of `#[derive(FromPyObject)]`, so that
+/// the same type definition can be converted in both directions.
+#[derive(Clone, Copy, Debug)]
+pub enum Conversion {
+    /// `#[derive(IntoPyObject)]`, implementing `IntoPy<PyObject>`.
+    IntoPy,
+    /// `#[derive(ToPyObject)]`, implementing `ToPyObject`.
+    ToPyObject,
+}
+
+impl Conversion {
+    fn derive_name(self) -> &'static str {
+        match self {
+            Conversion::IntoPy => "IntoPyObject",
+            Conversion::ToPyObject => "ToPyObject",
+        }
+    }
+
+    fn trait_path(self) -> TokenStream {
+        match self {
+            Conversion::IntoPy => quote!(pyo3::IntoPy<pyo3::PyObject>),
+            Conversion::ToPyObject => quote!(pyo3::ToPyObject),
+        }
+    }
+
+    /// Converts a field binding, which is a value for `IntoPy` and a reference for `ToPyObject`.
+    fn convert(self, value: &Ident) -> TokenStream {
+        match self {
+            Conversion::IntoPy => quote!(pyo3::IntoPy::<pyo3::PyObject>::into_py(#value, py)),
+            Conversion::ToPyObject => quote!(pyo3::ToPyObject::to_object(#value, py)),
+        }
+    }
+}
+
+/// Describes derivation input of an enum.
+struct Enum<'a> {
+    variants: Vec<Container<'a>>,
+}
+
+impl<'a> Enum<'a> {
+    fn new(data_enum: &'a DataEnum, ident: &'a Ident, conversion: Conversion) -> Result<Self> {
+        ensure_spanned!(
+            !data_enum.variants.is_empty(),
+            ident.span() => format!("cannot derive {} for empty enum", conversion.derive_name())
+        );
+        let variants = data_enum
+            .variants
+            .iter()
+            .map(|variant| {
+                let options = ContainerOptions::from_attrs(&variant.attrs)?;
+                let var_ident = &variant.ident;
+                Container::new(
+                    &variant.fields,
+                    parse_quote!(#ident::#var_ident),
+                    options,
+                    conversion,
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Enum { variants })
+    }
+
+    fn build(&self, conversion: Conversion) -> TokenStream {
+        let arms = self
+            .variants
+            .iter()
+            .map(|variant| variant.build_arm(conversion));
+        quote!(
+            match self {
+                #(#arms)*
+            }
+        )
+    }
+}
+
+/// Container Style, mirroring the container types accepted by `#[derive(FromPyObject)]`.
+enum ContainerType<'a> {
+    /// Struct Container, e.g. `struct Foo { a: String }`, converted to a `dict`.
+    Struct(Vec<StructField<'a>>),
+    /// Newtype struct container, e.g. `#[transparent] struct Foo { a: String }`, converted to
+    /// the Python object of its only field.
+    StructNewtype(&'a Ident),
+    /// Tuple struct, e.g. `struct Foo(String)`, converted to a `tuple`.
+    Tuple(usize),
+    /// Tuple newtype, e.g. `#[transparent] struct Foo(String)`, converted to the Python object
+    /// of its only field.
+    TupleNewtype,
+}
+
+/// Data container
+///
+/// Either describes a struct or an enum variant.
+struct Container<'a> {
+    path: syn::Path,
+    ty: ContainerType<'a>,
+}
+
+impl<'a> Container<'a> {
+    fn new(
+        fields: &'a Fields,
+        path: syn::Path,
+        options: ContainerOptions,
+        conversion: Conversion,
+    ) -> Result<Self> {
+        ensure_spanned!(
+            !fields.is_empty(),
+            fields.span() => format!(
+                "cannot derive {} for empty structs and variants",
+                conversion.derive_name()
+            )
+        );
+        if options.transparent {
+            ensure_spanned!(
+                fields.len() == 1,
+                fields.span() => "transparent structs and variants can only have 1 field"
+            );
+        }
+        let ty = match (fields, options.transparent) {
+            (Fields::Unnamed(_), true) => ContainerType::TupleNewtype,
+            (Fields::Unnamed(unnamed), false) => match unnamed.unnamed.len() {
+                1 => ContainerType::TupleNewtype,
+                len => ContainerType::Tuple(len),
+            },
+            (Fields::Named(named), true) => {
+                let field = named
+                    .named
+                    .iter()
+                    .next()
+                    .expect("Check for len 1 is done above");
+                let ident = field
+                    .ident
+                    .as_ref()
+                    .expect("Named fields should have identifiers");
+                ContainerType::StructNewtype(ident)
+            }
+            (Fields::Named(named), false) => ContainerType::Struct(
+                named
+                    .named
+                    .iter()
+                    .map(|field| StructField::new(field, &options))
+                    .collect::<Result<_>>()?,
+            ),
+            (Fields::Unit, _) => unreachable!(), // covered by length check above
+        };
+        Ok(Container { path, ty })
+    }
+
+    /// Builds a match arm which destructures the container and converts it.
+    fn build_arm(&self, conversion: Conversion) -> TokenStream {
+        let path = &self.path;
+        match &self.ty {
+            ContainerType::StructNewtype(ident) => {
+                let binding = field_binding(0);
+                let value = conversion.convert(&binding);
+                quote!(#path { #ident: #binding } => #value,)
+            }
+            ContainerType::TupleNewtype => {
+                let binding = field_binding(0);
+                let value = conversion.convert(&binding);
+                quote!(#path(#binding) => #value,)
+            }
+            ContainerType::Tuple(len) => {
+                let bindings: Vec<Ident> = (0..*len).map(field_binding).collect();
+                let values = bindings.iter().map(|binding| conversion.convert(binding));
+                quote!(
+                    #path(#(#bindings),*) => {
+                        let elements: [pyo3::PyObject; #len] = [#(#values),*];
+                        pyo3::types::PyTuple::new(py, &elements).into()
+                    }
+                )
+            }
+            ContainerType::Struct(fields) => {
+                let idents = fields.iter().map(|field| field.ident);
+                let bindings: Vec<Ident> = (0..fields.len()).map(field_binding).collect();
+                let set_items = fields.iter().zip(&bindings).map(|(field, binding)| {
+                    let key = match &field.access {
+                        FieldAccess::Item(key) => quote!(#key),
+                        FieldAccess::Attribute(name) => quote!(#name),
+                    };
+                    let value = conversion.convert(binding);
+                    quote!(dict.set_item(#key, #value).expect("Failed to set_item on dict");)
+                });
+                quote!(
+                    #path { #(#idents: #bindings),* } => {
+                        let dict = pyo3::types::PyDict::new(py);
+                        #(#set_items)*
+                        dict.into()
+                    }
+                )
+            }
+        }
+    }
+}
+
+fn field_binding(index: usize) -> Ident {
+    Ident::new(&format!("__field_{}", index), Span::call_site())
+}
+
+/// Derive `IntoPy<PyObject>` or `ToPyObject` for enums and structs.
+///
+///   * Structs with named fields are converted to a `dict`, keyed by the same `item` keys or
+///     `attribute` names used by `#[derive(FromPyObject)]`.
+///   * Tuple structs are converted to a `tuple`.
+///   * Newtypes and `#[pyo3(transparent)]` containers are converted to their only field.
+///   * Enums are converted according to the rules above for the variant they hold.
+///   * Derivation for structs with generic fields like `struct<T> Foo(T)` adds a bound on `T`
+///     for the derived conversion trait.
+pub fn build_derive_into_pyobject(
+    tokens: &DeriveInput,
+    conversion: Conversion,
+) -> Result<TokenStream> {
+    let body = match &tokens.data {
+        syn::Data::Enum(en) => Enum::new(en, &tokens.ident, conversion)?.build(conversion),
+        syn::Data::Struct(st) => {
+            let options = ContainerOptions::from_attrs(&tokens.attrs)?;
+            if let Some(lit_str) = &options.annotation {
+                bail_spanned!(lit_str.span() => "`annotation` is unsupported for structs");
+            }
+            let ident = &tokens.ident;
+            let container = Container::new(&st.fields, parse_quote!(#ident), options, conversion)?;
+            let arm = container.build_arm(conversion);
+            quote!(
+                match self {
+                    #arm
+                }
+            )
+        }
+        syn::Data::Union(_) => bail_spanned!(
+            tokens.span() => format!(
+                "#[derive({})] is not supported for unions",
+                conversion.derive_name()
+            )
+        ),
+    };
+
+    let trait_path = conversion.trait_path();
+    let mut generics = tokens.generics.clone();
+    {
+        let where_clause = generics.make_where_clause();
+        for param in tokens.generics.type_params() {
+            let gen_ident = &param.ident;
+            where_clause
+                .predicates
+                .push(parse_quote!(#gen_ident: #trait_path));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let ident = &tokens.ident;
+    let method = match conversion {
+        Conversion::IntoPy => quote!(fn into_py(self, py: pyo3::Python) -> pyo3::PyObject),
+        Conversion::ToPyObject => quote!(fn to_object(&self, py: pyo3::Python) -> pyo3::PyObject),
+    };
+    Ok(quote!(
+        #[automatically_derived]
+        impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+            #method {
+                #body
+            }
+        }
+    ))
+}
@@ -11,7 +11,9 @@ mod utils;
 mod attributes;
 mod defs;
 mod deprecations;
+mod element;
 mod from_pyobject;
+mod into_pyobject;
 mod konst;
 mod method;
 mod module;
@@ -23,7 +25,9 @@ mod pyimpl;
 mod pymethod;
 mod pyproto;
 
+pub use element::build_derive_element;
 pub use from_pyobject::build_derive_from_pyobject;
+pub use into_pyobject::{build_derive_into_pyobject, Conversion};
 pub use module::{process_functions_in_module, py_init, PyModuleOptions};
 pub use pyclass::{build_py_class, PyClassArgs};
 pub use pyfunction::{build_py_function, PyFunctionOptions};
@@ -367,6 +367,10 @@ impl<'a> FnSpec<'a> {
                 (FnType::FnNew, false, Some(CallingConvention::TpNew))
             }
             Some(MethodTypeAttribute::ClassMethod) => (FnType::FnClass, true, None),
+            // Like CPython, treat these as class methods even without `#[classmethod]`
+            None if is_implicit_classmethod(python_name.as_ref().unwrap_or(name)) => {
+                (FnType::FnClass, true, None)
+            }
             Some(MethodTypeAttribute::Call) => (
                 FnType::FnCall(parse_receiver("expected receiver for #[call]")?),
                 true,
@@ -583,6 +587,13 @@ struct MethodAttributes {
     python_name: Option<syn::Ident>,
 }
 
+/// Special methods which CPython implicitly converts to class methods when defined in a class
+/// body.
+fn is_implicit_classmethod(python_name: &syn::Ident) -> bool {
+    let name = python_name.unraw();
+    name == "__init_subclass__" || name == "__class_getitem__"
+}
+
 fn parse_method_attributes(
     attrs: &mut Vec<syn::Attribute>,
     mut python_name: Option<syn::Ident>,
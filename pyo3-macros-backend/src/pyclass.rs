@@ -20,12 +20,20 @@ pub struct PyClassArgs {
     pub freelist: Option<syn::Expr>,
     pub name: Option<syn::Ident>,
     pub base: syn::TypePath,
+    pub metaclass: Option<syn::TypePath>,
     pub has_dict: bool,
     pub has_weaklist: bool,
     pub is_gc: bool,
     pub is_basetype: bool,
     pub has_extends: bool,
     pub has_unsendable: bool,
+    pub has_eq: bool,
+    pub has_ord: bool,
+    pub has_hash: bool,
+    pub has_get_all: bool,
+    pub has_set_all: bool,
+    pub has_repr: bool,
+    pub pickle: Option<Span>,
     pub module: Option<syn::LitStr>,
 }
 
@@ -34,9 +42,10 @@ impl Parse for PyClassArgs {
         let mut slf = PyClassArgs::default();
 
         let vars = Punctuated::<Expr, Token![,]>::parse_terminated(input)?;
-        for expr in vars {
-            slf.add_expr(&expr)?;
+        for expr in &vars {
+            slf.add_expr(expr)?;
         }
+        slf.validate(&vars)?;
         Ok(slf)
     }
 }
@@ -48,12 +57,20 @@ impl Default for PyClassArgs {
             name: None,
             module: None,
             base: parse_quote! { pyo3::PyAny },
+            metaclass: None,
             has_dict: false,
             has_weaklist: false,
             is_gc: false,
             is_basetype: false,
             has_extends: false,
             has_unsendable: false,
+            has_eq: false,
+            has_ord: false,
+            has_hash: false,
+            has_get_all: false,
+            has_set_all: false,
+            has_repr: false,
+            pickle: None,
         }
     }
 }
@@ -124,6 +141,15 @@ impl PyClassArgs {
                 }
                 _ => expected!("type path (e.g., my_mod::BaseClass)"),
             },
+            "metaclass" => match unwrap_group(&**right) {
+                syn::Expr::Path(exp) => {
+                    self.metaclass = Some(syn::TypePath {
+                        path: exp.path.clone(),
+                        qself: None,
+                    });
+                }
+                _ => expected!("type path (e.g., my_mod::Metaclass)"),
+            },
             "module" => match unwrap_group(&**right) {
                 syn::Expr::Lit(syn::ExprLit {
                     lit: syn::Lit::Str(lit),
@@ -133,7 +159,7 @@ impl PyClassArgs {
                 }
                 _ => expected!(r#"string literal (e.g., "my_mod")"#),
             },
-            _ => expected!("one of freelist/name/extends/module", left.span()),
+            _ => expected!("one of freelist/name/extends/metaclass/module", left.span()),
         };
 
         Ok(())
@@ -158,12 +184,61 @@ impl PyClassArgs {
             "unsendable" => {
                 self.has_unsendable = true;
             }
+            "eq" => {
+                self.has_eq = true;
+            }
+            "ord" => {
+                self.has_ord = true;
+            }
+            "hash" => {
+                self.has_hash = true;
+            }
+            "get_all" => {
+                self.has_get_all = true;
+            }
+            "set_all" => {
+                self.has_set_all = true;
+            }
+            "repr" => {
+                self.has_repr = true;
+            }
+            "pickle" => {
+                self.pickle = Some(exp.span());
+            }
             _ => bail_spanned!(
-                exp.path.span() => "expected one of gc/weakref/subclass/dict/unsendable"
+                exp.path.span() => "expected one of gc/weakref/subclass/dict/unsendable/eq/ord/hash/get_all/set_all/repr/pickle"
             ),
         };
         Ok(())
     }
+
+    /// Check for flags which are only meaningful in combination with other flags
+    fn validate(&self, vars: &Punctuated<Expr, Token![,]>) -> syn::Result<()> {
+        if let Some(span) = self.pickle {
+            // Unpickling looks the class up by its module, and only creates the Rust value.
+            ensure_spanned!(
+                self.module.is_some(),
+                span => "the `pickle` option requires the `module` option"
+            );
+            ensure_spanned!(
+                !self.has_extends,
+                span => "the `pickle` option cannot be combined with `extends`"
+            );
+        }
+        if self.has_eq {
+            return Ok(());
+        }
+        for expr in vars {
+            if let syn::Expr::Path(exp) = expr {
+                if let Some(flag) = exp.path.get_ident().filter(|i| *i == "ord" || *i == "hash") {
+                    bail_spanned!(
+                        exp.span() => format!("the `{}` option requires the `eq` option", flag)
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Default)]
@@ -237,7 +312,7 @@ pub fn build_py_class(
         class.generics.span() => "#[pyclass] cannot have generic parameters"
     );
 
-    let field_options = match &mut class.fields {
+    let mut field_options: Vec<(&syn::Field, FieldPyO3Options)> = match &mut class.fields {
         syn::Fields::Named(fields) => fields
             .named
             .iter_mut()
@@ -260,6 +335,23 @@ pub fn build_py_class(
         }
     };
 
+    for (field, options) in &mut field_options {
+        if args.has_get_all {
+            ensure_spanned!(
+                !options.get,
+                field.span() => "useless `get` - the struct is already annotated with `get_all`"
+            );
+            options.get = true;
+        }
+        if args.has_set_all {
+            ensure_spanned!(
+                !options.set,
+                field.span() => "useless `set` - the struct is already annotated with `set_all`"
+            );
+            options.set = true;
+        }
+    }
+
     impl_class(
         &class.ident,
         args,
@@ -410,6 +502,8 @@ fn impl_class(
             }
         });
 
+    let default_slots = impl_default_slots(cls, attr, &field_options);
+    let pickle = attr.pickle.map(|_| impl_pickle_state(cls, &field_options));
     let descriptors = impl_descriptors(cls, field_options)?;
 
     // insert space for weak ref
@@ -462,6 +556,10 @@ fn impl_class(
     };
 
     let base = &attr.base;
+    let metaclass = match &attr.metaclass {
+        Some(metaclass) => quote! { #metaclass },
+        None => quote! { <#base as pyo3::class::impl_::PyClassBaseType>::Metaclass },
+    };
     let base_nativetype = if attr.has_extends {
         quote! { <Self::BaseType as pyo3::class::impl_::PyClassBaseType>::BaseNativeType }
     } else {
@@ -540,6 +638,7 @@ fn impl_class(
 
             type Layout = pyo3::PyCell<Self>;
             type BaseType = #base;
+            type Metaclass = #metaclass;
             type ThreadChecker = #thread_checker;
 
             fn for_each_method_def(visitor: &mut dyn FnMut(&[pyo3::class::PyMethodDefType])) {
@@ -553,6 +652,8 @@ fn impl_class(
                 visitor(collector.descr_protocol_methods());
                 visitor(collector.mapping_protocol_methods());
                 visitor(collector.number_protocol_methods());
+                visitor(collector.tzinfo_methods());
+                visitor(collector.pickle_methods());
             }
             fn get_new() -> Option<pyo3::ffi::newfunc> {
                 use pyo3::class::impl_::*;
@@ -579,6 +680,7 @@ fn impl_class(
                 // Implementation which uses dtolnay specialization to load all slots.
                 use pyo3::class::impl_::*;
                 let collector = PyClassImplCollector::<Self>::new();
+                #default_slots
                 visitor(collector.object_protocol_slots());
                 visitor(collector.number_protocol_slots());
                 visitor(collector.iter_protocol_slots());
@@ -601,10 +703,155 @@ fn impl_class(
 
         #descriptors
 
+        #pickle
+
         #gc_impl
     })
 }
 
+/// Slots generated by the `eq`, `ord`, `hash` and `repr` options.
+fn impl_default_slots(
+    cls: &syn::Ident,
+    attr: &PyClassArgs,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+) -> TokenStream {
+    let mut slots = Vec::new();
+
+    if attr.has_eq {
+        let richcmp = if attr.has_ord {
+            quote! { pyo3::class::basic::richcmp_from_ord::<#cls> }
+        } else {
+            quote! { pyo3::class::basic::richcmp_from_eq::<#cls> }
+        };
+        // Python sets `__hash__ = None` on classes which define `__eq__` without `__hash__`.
+        let hash = if attr.has_hash {
+            quote! { pyo3::class::basic::hash_from_hash::<#cls> }
+        } else {
+            quote! { pyo3::ffi::PyObject_HashNotImplemented }
+        };
+        slots.push(quote! {
+            pyo3::ffi::PyType_Slot {
+                slot: pyo3::ffi::Py_tp_richcompare,
+                pfunc: #richcmp as _
+            }
+        });
+        slots.push(quote! {
+            pyo3::ffi::PyType_Slot {
+                slot: pyo3::ffi::Py_tp_hash,
+                pfunc: #hash as _
+            }
+        });
+    }
+
+    if attr.has_repr {
+        slots.push(impl_repr_slot(cls, field_options));
+    }
+
+    if slots.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        visitor(&[#(#slots),*]);
+    }
+}
+
+/// Generates a dataclass-style `__repr__` showing every field readable from Python.
+fn impl_repr_slot(
+    cls: &syn::Ident,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+) -> TokenStream {
+    let fields: Vec<TokenStream> = field_options
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, options))| options.get)
+        .map(|(field_index, (field, options))| {
+            let member = match &field.ident {
+                Some(ident) => quote!(#ident),
+                None => {
+                    let index = syn::Index::from(field_index);
+                    quote!(#index)
+                }
+            };
+            // Tuple fields without `name` are rejected when generating their getter.
+            let python_name = match (&options.name, &field.ident) {
+                (Some(name), _) => name.0.to_string(),
+                (None, Some(ident)) => ident.unraw().to_string(),
+                (None, None) => field_index.to_string(),
+            };
+            quote! {
+                (#python_name, pyo3::IntoPy::<pyo3::PyObject>::into_py(_slf.#member.clone(), _py))
+            }
+        })
+        .collect();
+    let field_count = fields.len();
+
+    quote! {
+        pyo3::ffi::PyType_Slot {
+            slot: pyo3::ffi::Py_tp_repr,
+            pfunc: {
+                unsafe extern "C" fn __wrap(
+                    _slf: *mut pyo3::ffi::PyObject
+                ) -> *mut pyo3::ffi::PyObject {
+                    pyo3::callback::handle_panic(|_py| {
+                        let _cell = _py.from_borrowed_ptr::<pyo3::PyCell<#cls>>(_slf);
+                        let _fields: [(&str, pyo3::PyObject); #field_count] = {
+                            let _slf = _cell.try_borrow()?;
+                            [#(#fields),*]
+                        };
+                        pyo3::callback::convert(
+                            _py,
+                            pyo3::class::basic::dataclass_repr(_cell.as_ref(), &_fields)
+                        )
+                    })
+                }
+                __wrap
+            } as _
+        }
+    }
+}
+
+/// Implements `PickleState`, which provides `__reduce__` and `__setstate__` for the
+/// `pickle` option.
+fn impl_pickle_state(
+    cls: &syn::Ident,
+    field_options: &[(&syn::Field, FieldPyO3Options)],
+) -> TokenStream {
+    let members: Vec<TokenStream> = field_options
+        .iter()
+        .enumerate()
+        .map(|(field_index, (field, _))| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = syn::Index::from(field_index);
+                quote!(#index)
+            }
+        })
+        .collect();
+    let indices = 0..members.len();
+    let field_count = members.len();
+
+    quote! {
+        impl pyo3::class::pickle::PickleState for #cls {
+            fn to_state(&self, py: pyo3::Python) -> pyo3::PyResult<pyo3::PyObject> {
+                // a `Vec` rather than an array, which only implements `IntoIterator` for up to
+                // 32 elements before Rust 1.47
+                let _fields: ::std::vec::Vec<pyo3::PyObject> = ::std::vec![
+                    #(pyo3::IntoPy::<pyo3::PyObject>::into_py(self.#members.clone(), py)),*
+                ];
+                Ok(pyo3::types::PyTuple::new(py, _fields).into())
+            }
+
+            fn from_state(state: &pyo3::PyAny) -> pyo3::PyResult<Self> {
+                let _state = pyo3::class::pickle::fields_state(state, #field_count)?;
+                Ok(Self {
+                    #(#members: _state.get_item(#indices).extract()?,)*
+                })
+            }
+        }
+    }
+}
+
 fn impl_descriptors(
     cls: &syn::Ident,
     field_options: Vec<(&syn::Field, FieldPyO3Options)>,
@@ -7,9 +7,9 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use pyo3_macros_backend::{
-    build_derive_from_pyobject, build_py_class, build_py_function, build_py_methods,
-    build_py_proto, get_doc, process_functions_in_module, py_init, PyClassArgs, PyClassMethodsType,
-    PyFunctionOptions, PyModuleOptions,
+    build_derive_element, build_derive_from_pyobject, build_derive_into_pyobject, build_py_class,
+    build_py_function, build_py_methods, build_py_proto, get_doc, process_functions_in_module,
+    py_init, Conversion, PyClassArgs, PyClassMethodsType, PyFunctionOptions, PyModuleOptions,
 };
 use quote::quote;
 use syn::parse_macro_input;
@@ -267,6 +267,38 @@ pub fn derive_from_py_object(item: TokenStream) -> TokenStream {
     .into()
 }
 
+#[proc_macro_derive(Element)]
+pub fn derive_element(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    let expanded = build_derive_element(&ast).unwrap_or_else(|e| e.to_compile_error());
+    quote!(
+        #expanded
+    )
+    .into()
+}
+
+#[proc_macro_derive(IntoPyObject, attributes(pyo3))]
+pub fn derive_into_py_object(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    let expanded = build_derive_into_pyobject(&ast, Conversion::IntoPy)
+        .unwrap_or_else(|e| e.to_compile_error());
+    quote!(
+        #expanded
+    )
+    .into()
+}
+
+#[proc_macro_derive(ToPyObject, attributes(pyo3))]
+pub fn derive_to_py_object(item: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(item as syn::DeriveInput);
+    let expanded = build_derive_into_pyobject(&ast, Conversion::ToPyObject)
+        .unwrap_or_else(|e| e.to_compile_error());
+    quote!(
+        #expanded
+    )
+    .into()
+}
+
 fn pyclass_impl(
     attr: TokenStream,
     input: TokenStream,
@@ -27,6 +27,18 @@ use std::pin::Pin;
 use std::{cell, mem, ptr, slice};
 use std::{ffi::CStr, fmt::Debug};
 
+mod layout;
+mod view;
+
+pub use self::layout::{ByteOrder, ElementLayout, Field};
+pub use self::view::{BufferView, Rows};
+
+/// Derives [`Element`] for a `#[repr(C)]` struct whose fields all implement `Element`, so that
+/// arrays of records, such as NumPy structured arrays, can be read without copying.
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use pyo3_macros::Element;
+
 /// Allows access to the underlying buffer used by a python object such as `bytes`, `bytearray` or `array.array`.
 // use Pin<Box> because Python expects that the Py_buffer struct has a stable memory address
 #[repr(transparent)]
@@ -56,7 +68,7 @@ impl<T> Debug for PyBuffer<T> {
 }
 
 /// Represents the type of a Python buffer element.
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ElementType {
     /// A signed integer type and its width in bytes.
     SignedInteger { bytes: usize },
@@ -66,23 +78,20 @@ pub enum ElementType {
     Bool,
     /// A float type and its width in bytes.
     Float { bytes: usize },
+    /// A complex type and its width in bytes, which is twice the width of its parts.
+    Complex { bytes: usize },
     /// An unknown type. This may occur when parsing has failed.
     Unknown,
 }
 
 impl ElementType {
     /// Determines the `ElementType` from a Python `struct` module format string.
+    ///
+    /// Returns `Unknown` unless the format string describes a single scalar. Use
+    /// [`ElementLayout::parse`] to get the full layout of other elements.
     pub fn from_format(format: &CStr) -> ElementType {
-        match format.to_bytes() {
-            [char] | [b'@', char] => native_element_type_from_type_char(*char),
-            [modifier, char]
-                if (*modifier == b'='
-                    || *modifier == b'<'
-                    || *modifier == b'>'
-                    || *modifier == b'!') =>
-            {
-                standard_element_type_from_type_char(*char)
-            }
+        match format.to_str().ok().map(ElementLayout::parse) {
+            Some(Ok(ElementLayout::Scalar { ty, .. })) => ty,
             _ => ElementType::Unknown,
         }
     }
@@ -157,21 +166,38 @@ fn standard_element_type_from_type_char(type_char: u8) -> ElementType {
     }
 }
 
-#[cfg(target_endian = "little")]
-fn is_matching_endian(c: u8) -> bool {
-    c == b'@' || c == b'=' || c == b'>'
-}
-
-#[cfg(target_endian = "big")]
-fn is_matching_endian(c: u8) -> bool {
-    c == b'@' || c == b'=' || c == b'>' || c == b'!'
-}
-
 /// Trait implemented for possible element types of `PyBuffer`.
+///
+/// For `#[repr(C)]` structs, this trait can be implemented with `#[derive(Element)]`.
+///
+/// # Safety
+///
+/// Any layout returned by `layout()` must describe the size and memory representation of the
+/// type exactly, and any bit pattern accepted by `is_compatible_format` must be a valid value of
+/// the type.
 pub unsafe trait Element: Copy {
+    /// Returns the layout of this type, which also gives the format of buffers exported with
+    /// [`BufferExport`](crate::class::buffer::BufferExport).
+    ///
+    /// Defaults to `None`, in which case `is_compatible_format` has to be implemented instead, and
+    /// the type cannot be exported.
+    fn layout() -> Option<ElementLayout> {
+        None
+    }
+
     /// Gets whether the element specified in the format string is potentially compatible.
     /// Alignment and size are checked separately from this function.
-    fn is_compatible_format(format: &CStr) -> bool;
+    ///
+    /// By default, the format is parsed and compared against `layout()`.
+    fn is_compatible_format(format: &CStr) -> bool {
+        match (
+            format.to_str().ok().map(ElementLayout::parse),
+            Self::layout(),
+        ) {
+            (Some(Ok(layout)), Some(expected)) => layout.is_compatible(&expected),
+            _ => false,
+        }
+    }
 }
 
 impl<'source, T: Element> FromPyObject<'source> for PyBuffer<T> {
@@ -622,12 +648,11 @@ impl<T: Element> ReadOnlyCell<T> {
 macro_rules! impl_element(
     ($t:ty, $f:ident) => {
         unsafe impl Element for $t {
-            fn is_compatible_format(format: &CStr) -> bool {
-                let slice = format.to_bytes();
-                if slice.len() > 1 && !is_matching_endian(slice[0]) {
-                    return false;
-                }
-                ElementType::from_format(format) == ElementType::$f { bytes: mem::size_of::<$t>() }
+            fn layout() -> Option<ElementLayout> {
+                Some(ElementLayout::Scalar {
+                    ty: ElementType::$f { bytes: mem::size_of::<$t>() },
+                    byte_order: ByteOrder::NATIVE,
+                })
             }
         }
     }
@@ -646,11 +671,100 @@ impl_element!(isize, SignedInteger);
 impl_element!(f32, Float);
 impl_element!(f64, Float);
 
+#[cfg_attr(docsrs, doc(cfg(feature = "half")))]
+#[cfg(feature = "half")]
+impl_element!(half::f16, Float);
+
+#[cfg_attr(docsrs, doc(cfg(feature = "num-complex")))]
+#[cfg(feature = "num-complex")]
+impl_element!(num_complex::Complex<f32>, Complex);
+
+#[cfg_attr(docsrs, doc(cfg(feature = "num-complex")))]
+#[cfg(feature = "num-complex")]
+impl_element!(num_complex::Complex<f64>, Complex);
+
+#[cfg(min_const_generics)]
+unsafe impl<T: Element, const N: usize> Element for [T; N] {
+    fn layout() -> Option<ElementLayout> {
+        T::layout().map(|layout| ElementLayout::array(N, layout))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::PyBuffer;
+    use super::{Element, PyBuffer};
     use crate::ffi;
     use crate::Python;
+    use std::ffi::CString;
+
+    fn is_compatible<T: Element>(format: &str) -> bool {
+        T::is_compatible_format(&CString::new(format).unwrap())
+    }
+
+    #[test]
+    fn test_element_formats() {
+        assert!(is_compatible::<u32>("I"));
+        assert!(is_compatible::<u32>("=I"));
+        assert!(is_compatible::<i32>("=l"));
+        assert!(!is_compatible::<u32>("i"));
+        assert!(!is_compatible::<u32>("T{I:a:I:b:}"));
+        assert!(!is_compatible::<u32>("not a format"));
+        #[cfg(target_endian = "little")]
+        {
+            assert!(is_compatible::<u32>("<I"));
+            assert!(!is_compatible::<u32>(">I"));
+            assert!(!is_compatible::<u32>("!I"));
+        }
+        #[cfg(target_endian = "big")]
+        {
+            assert!(!is_compatible::<u32>("<I"));
+            assert!(is_compatible::<u32>(">I"));
+        }
+    }
+
+    #[test]
+    fn test_element_without_layout() {
+        #[derive(Clone, Copy)]
+        struct Raw(u32);
+
+        unsafe impl Element for Raw {
+            fn is_compatible_format(format: &std::ffi::CStr) -> bool {
+                format.to_bytes() == b"I"
+            }
+        }
+
+        assert!(Raw::layout().is_none());
+        assert!(is_compatible::<Raw>("I"));
+        assert!(!is_compatible::<Raw>("i"));
+    }
+
+    #[cfg(min_const_generics)]
+    #[test]
+    fn test_array_element() {
+        assert_eq!(<[i16; 3]>::layout().unwrap().to_string(), "(3)h");
+        assert_eq!(<[[f32; 2]; 4]>::layout().unwrap().to_string(), "(4,2)f");
+        assert!(is_compatible::<[u8; 4]>("(4)B"));
+        assert!(!is_compatible::<[u8; 4]>("4s"));
+    }
+
+    #[cfg(feature = "num-complex")]
+    #[test]
+    fn test_complex_element() {
+        use num_complex::Complex;
+        assert!(is_compatible::<Complex<f64>>("Zd"));
+        assert!(is_compatible::<Complex<f32>>("Zf"));
+        assert!(!is_compatible::<Complex<f32>>("Zd"));
+        assert!(!is_compatible::<Complex<f64>>("d"));
+        assert_eq!(Complex::<f64>::layout().unwrap().to_string(), "Zd");
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn test_f16_element() {
+        assert!(is_compatible::<half::f16>("e"));
+        assert!(!is_compatible::<half::f16>("H"));
+        assert_eq!(half::f16::layout().unwrap().size(), 2);
+    }
 
     #[test]
     fn test_compatible_size() {
@@ -0,0 +1,718 @@
+//! Parsing of `struct` module format strings into element layouts.
+use super::ElementType;
+use crate::exceptions::PyValueError;
+use crate::PyResult;
+use std::{fmt, mem, os::raw};
+
+/// The byte order of a scalar buffer element.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ByteOrder {
+    /// Little-endian, from the `<` format modifier.
+    LittleEndian,
+    /// Big-endian, from the `>` and `!` format modifiers.
+    BigEndian,
+}
+
+impl ByteOrder {
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: ByteOrder = ByteOrder::LittleEndian;
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: ByteOrder = ByteOrder::BigEndian;
+}
+
+/// The layout of a buffer element, described by a `struct` module format string with the
+/// extensions of [PEP 3118](https://www.python.org/dev/peps/pep-3118/).
+///
+/// Its `Display` implementation writes the layout back as a format string.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::buffer::{ByteOrder, ElementLayout, ElementType};
+///
+/// let layout = ElementLayout::parse("T{<i:x:4x>d:y:}").unwrap();
+/// assert_eq!(layout.size(), 16);
+/// if let ElementLayout::Struct { fields, .. } = &layout {
+///     assert_eq!(fields[1].name.as_deref(), Some("y"));
+///     assert_eq!(fields[1].offset, 8);
+///     assert_eq!(
+///         fields[1].layout,
+///         ElementLayout::Scalar {
+///             ty: ElementType::Float { bytes: 8 },
+///             byte_order: ByteOrder::BigEndian,
+///         }
+///     );
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ElementLayout {
+    /// A number or boolean.
+    Scalar {
+        ty: ElementType,
+        byte_order: ByteOrder,
+    },
+    /// A fixed-length byte string, from the `s` and `p` format codes.
+    Bytes { len: usize },
+    /// A pointer to a Python object, from the `O` format code.
+    Object,
+    /// A multi-dimensional array of elements, such as `(2,3)d`.
+    Array {
+        shape: Vec<usize>,
+        element: Box<ElementLayout>,
+    },
+    /// A structure, such as `T{i:x:d:y:}`, and its size including all padding.
+    Struct { fields: Vec<Field>, size: usize },
+}
+
+/// A field of an [`ElementLayout::Struct`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Field {
+    /// The name of the field, if the format string gives one.
+    pub name: Option<String>,
+    /// The offset of the field from the start of the structure, in bytes.
+    pub offset: usize,
+    /// The layout of the field.
+    pub layout: ElementLayout,
+}
+
+impl ElementLayout {
+    /// Parses a `struct` module format string.
+    ///
+    /// A format string describing a single unnamed item results in the layout of that item,
+    /// while all other format strings result in an [`ElementLayout::Struct`]. Returns `ValueError`
+    /// if the format string is invalid or uses unsupported format codes.
+    pub fn parse(format: &str) -> PyResult<ElementLayout> {
+        let mut parser = Parser {
+            format,
+            input: format.as_bytes(),
+            pos: 0,
+        };
+        let (mut fields, size, _) = parser.parse_items(Mode::NATIVE, false)?;
+        if fields.is_empty() && size == 0 {
+            return Err(parser.error("the format is empty"));
+        }
+        if fields.len() == 1
+            && fields[0].name.is_none()
+            && fields[0].offset == 0
+            && fields[0].layout.size() == size
+        {
+            return Ok(fields.remove(0).layout);
+        }
+        Ok(ElementLayout::Struct { fields, size })
+    }
+
+    /// Returns the size of the element in bytes.
+    ///
+    /// Saturates at `usize::MAX` for arrays too large to be addressed, which [`parse`] rejects.
+    ///
+    /// [`parse`]: ElementLayout::parse
+    pub fn size(&self) -> usize {
+        self.checked_size().unwrap_or(usize::MAX)
+    }
+
+    fn checked_size(&self) -> Option<usize> {
+        match self {
+            ElementLayout::Scalar { ty, .. } => Some(scalar_size(*ty)),
+            ElementLayout::Bytes { len } => Some(*len),
+            ElementLayout::Object => Some(mem::size_of::<*mut raw::c_void>()),
+            ElementLayout::Array { shape, element } => shape
+                .iter()
+                .try_fold(element.checked_size()?, |size, &len| size.checked_mul(len)),
+            ElementLayout::Struct { size, .. } => Some(*size),
+        }
+    }
+
+    /// Checks whether elements of this layout can be read as elements of the `other` layout.
+    ///
+    /// Field names are ignored, but their offsets have to match.
+    pub fn is_compatible(&self, other: &ElementLayout) -> bool {
+        match (self, other) {
+            (
+                ElementLayout::Array { shape, element },
+                ElementLayout::Array {
+                    shape: other_shape,
+                    element: other_element,
+                },
+            ) => shape == other_shape && element.is_compatible(other_element),
+            (
+                ElementLayout::Struct { fields, size },
+                ElementLayout::Struct {
+                    fields: other_fields,
+                    size: other_size,
+                },
+            ) => {
+                size == other_size
+                    && fields.len() == other_fields.len()
+                    && fields.iter().zip(other_fields).all(|(field, other)| {
+                        field.offset == other.offset && field.layout.is_compatible(&other.layout)
+                    })
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Builds the layout of a `#[repr(C)]` struct from its size and its fields' names, layouts and
+    /// alignments. Used by `#[derive(Element)]`.
+    #[doc(hidden)]
+    pub fn repr_c(size: usize, fields: Vec<(&str, ElementLayout, usize)>) -> ElementLayout {
+        let mut offset = 0;
+        let fields = fields
+            .into_iter()
+            .map(|(name, layout, align)| {
+                offset = round_up(offset, align);
+                let field = Field {
+                    name: Some(name.to_owned()),
+                    offset,
+                    layout,
+                };
+                offset += field.layout.size();
+                field
+            })
+            .collect();
+        ElementLayout::Struct { fields, size }
+    }
+
+    /// Builds the layout of an array of `len` elements, merging nested arrays into one shape.
+    pub(crate) fn array(len: usize, element: ElementLayout) -> ElementLayout {
+        match element {
+            ElementLayout::Array { mut shape, element } => {
+                shape.insert(0, len);
+                ElementLayout::Array { shape, element }
+            }
+            element => ElementLayout::Array {
+                shape: vec![len],
+                element: Box::new(element),
+            },
+        }
+    }
+
+    fn write_format(&self, f: &mut fmt::Formatter, in_struct: bool) -> fmt::Result {
+        match self {
+            ElementLayout::Scalar { ty, byte_order } => {
+                // Structs are written without implicit alignment, so their fields need a modifier.
+                if *byte_order != ByteOrder::NATIVE {
+                    f.write_str(match byte_order {
+                        ByteOrder::LittleEndian => "<",
+                        ByteOrder::BigEndian => ">",
+                    })?;
+                } else if in_struct {
+                    f.write_str("=")?;
+                }
+                match scalar_code(*ty) {
+                    Some(code) => f.write_str(code),
+                    // not expressible in a format string, so written as opaque bytes
+                    None => write!(f, "{}x", scalar_size(*ty)),
+                }
+            }
+            ElementLayout::Bytes { len } => write!(f, "{}s", len),
+            ElementLayout::Object => f.write_str("O"),
+            ElementLayout::Array { shape, element } => {
+                f.write_str("(")?;
+                for (i, dim) in shape.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", dim)?;
+                }
+                f.write_str(")")?;
+                element.write_format(f, in_struct)
+            }
+            ElementLayout::Struct { fields, size } => {
+                f.write_str("T{")?;
+                let mut end = 0;
+                for field in fields {
+                    if field.offset > end {
+                        write!(f, "{}x", field.offset - end)?;
+                    }
+                    field.layout.write_format(f, true)?;
+                    if let Some(name) = &field.name {
+                        write!(f, ":{}:", name)?;
+                    }
+                    end = field.offset + field.layout.size();
+                }
+                if *size > end {
+                    write!(f, "{}x", size - end)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ElementLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_format(f, false)
+    }
+}
+
+fn scalar_size(ty: ElementType) -> usize {
+    match ty {
+        ElementType::SignedInteger { bytes }
+        | ElementType::UnsignedInteger { bytes }
+        | ElementType::Float { bytes }
+        | ElementType::Complex { bytes } => bytes,
+        ElementType::Bool => 1,
+        ElementType::Unknown => 0,
+    }
+}
+
+/// Returns the standard format code of a scalar type.
+fn scalar_code(ty: ElementType) -> Option<&'static str> {
+    use ElementType::*;
+    Some(match ty {
+        SignedInteger { bytes: 1 } => "b",
+        SignedInteger { bytes: 2 } => "h",
+        SignedInteger { bytes: 4 } => "i",
+        SignedInteger { bytes: 8 } => "q",
+        UnsignedInteger { bytes: 1 } => "B",
+        UnsignedInteger { bytes: 2 } => "H",
+        UnsignedInteger { bytes: 4 } => "I",
+        UnsignedInteger { bytes: 8 } => "Q",
+        Bool => "?",
+        Float { bytes: 2 } => "e",
+        Float { bytes: 4 } => "f",
+        Float { bytes: 8 } => "d",
+        Complex { bytes: 4 } => "Ze",
+        Complex { bytes: 8 } => "Zf",
+        Complex { bytes: 16 } => "Zd",
+        _ => return None,
+    })
+}
+
+fn round_up(offset: usize, align: usize) -> usize {
+    (offset + align - 1) / align * align
+}
+
+fn checked_round_up(offset: usize, align: usize) -> Option<usize> {
+    Some(offset.checked_add(align - 1)? / align * align)
+}
+
+/// The state set by the byte order, size and alignment modifier.
+#[derive(Copy, Clone)]
+struct Mode {
+    byte_order: ByteOrder,
+    native_size: bool,
+    aligned: bool,
+}
+
+impl Mode {
+    const NATIVE: Mode = Mode {
+        byte_order: ByteOrder::NATIVE,
+        native_size: true,
+        aligned: true,
+    };
+
+    fn from_modifier(modifier: u8) -> Option<Mode> {
+        let (byte_order, native_size, aligned) = match modifier {
+            b'@' => (ByteOrder::NATIVE, true, true),
+            // native sizes without alignment, from PEP 3118
+            b'^' => (ByteOrder::NATIVE, true, false),
+            b'=' => (ByteOrder::NATIVE, false, false),
+            b'<' => (ByteOrder::LittleEndian, false, false),
+            b'>' | b'!' => (ByteOrder::BigEndian, false, false),
+            _ => return None,
+        };
+        Some(Mode {
+            byte_order,
+            native_size,
+            aligned,
+        })
+    }
+}
+
+struct Parser<'a> {
+    format: &'a str,
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, msg: &str) -> crate::PyErr {
+        PyValueError::new_err(format!("invalid buffer format {:?}: {}", self.format, msg))
+    }
+
+    fn too_large(&self) -> crate::PyErr {
+        self.error("the element is too large")
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let c = self.peek();
+        self.pos += 1;
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().map_or(false, |c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_number(&mut self) -> PyResult<Option<usize>> {
+        let start = self.pos;
+        while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        self.format[start..self.pos]
+            .parse()
+            .map(Some)
+            .map_err(|_| self.error("repeat count is too large"))
+    }
+
+    /// Parses an array shape such as `(2,3)`.
+    fn parse_shape(&mut self) -> PyResult<Vec<usize>> {
+        let mut shape = Vec::new();
+        if self.peek() != Some(b'(') {
+            return Ok(shape);
+        }
+        self.pos += 1;
+        loop {
+            self.skip_whitespace();
+            match self.parse_number()? {
+                Some(dim) => shape.push(dim),
+                None => return Err(self.error("expected an array dimension")),
+            }
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => {}
+                Some(b')') => return Ok(shape),
+                _ => return Err(self.error("expected ',' or ')' in array shape")),
+            }
+        }
+    }
+
+    /// Parses a field name such as `:x:`.
+    fn parse_name(&mut self) -> PyResult<Option<String>> {
+        if self.peek() != Some(b':') {
+            return Ok(None);
+        }
+        let start = self.pos + 1;
+        match self.input[start..].iter().position(|&c| c == b':') {
+            Some(len) => {
+                self.pos = start + len + 1;
+                Ok(Some(self.format[start..start + len].to_owned()))
+            }
+            None => Err(self.error("unterminated field name")),
+        }
+    }
+
+    /// Parses items up to the end of the input, or up to the closing `}` of a struct.
+    ///
+    /// Returns the items as fields, and the size and alignment of the struct they form.
+    fn parse_items(
+        &mut self,
+        mut mode: Mode,
+        in_struct: bool,
+    ) -> PyResult<(Vec<Field>, usize, usize)> {
+        let mut fields = Vec::new();
+        let (mut offset, mut struct_align) = (0usize, 1);
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None if in_struct => return Err(self.error("unterminated 'T{'")),
+                None => break,
+                Some(b'}') if in_struct => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => {}
+            }
+
+            let mut shape = self.parse_shape()?;
+            if let Some(new_mode) = self.peek().and_then(Mode::from_modifier) {
+                self.pos += 1;
+                mode = new_mode;
+                if shape.is_empty() {
+                    continue;
+                }
+            }
+            let count = self.parse_number()?;
+            let code = self
+                .next()
+                .ok_or_else(|| self.error("expected a format code"))?;
+            let (layout, align) = match code {
+                b'x' => {
+                    offset = shape
+                        .iter()
+                        .try_fold(count.unwrap_or(1), |len, &dim| len.checked_mul(dim))
+                        .and_then(|len| offset.checked_add(len))
+                        .ok_or_else(|| self.too_large())?;
+                    continue;
+                }
+                b's' | b'p' => (
+                    ElementLayout::Bytes {
+                        len: count.unwrap_or(1),
+                    },
+                    1,
+                ),
+                b'T' => {
+                    if self.next() != Some(b'{') {
+                        return Err(self.error("expected '{' after 'T'"));
+                    }
+                    let (fields, size, align) = self.parse_items(mode, true)?;
+                    (ElementLayout::Struct { fields, size }, align)
+                }
+                b'O' => (ElementLayout::Object, mem::align_of::<*mut raw::c_void>()),
+                b'Z' => {
+                    let component = self
+                        .next()
+                        .map_or(ElementType::Unknown, |c| scalar_type(c, mode.native_size));
+                    match component {
+                        ElementType::Float { bytes } => (
+                            ElementLayout::Scalar {
+                                ty: ElementType::Complex { bytes: 2 * bytes },
+                                byte_order: mode.byte_order,
+                            },
+                            bytes,
+                        ),
+                        _ => return Err(self.error("expected 'e', 'f' or 'd' after 'Z'")),
+                    }
+                }
+                code => match scalar_type(code, mode.native_size) {
+                    ElementType::Unknown => {
+                        return Err(
+                            self.error(&format!("unsupported format code {:?}", char::from(code)))
+                        )
+                    }
+                    ty => (
+                        ElementLayout::Scalar {
+                            ty,
+                            byte_order: mode.byte_order,
+                        },
+                        scalar_size(ty),
+                    ),
+                },
+            };
+            if !is_string_code(code) {
+                shape.extend(count);
+            }
+            let layout = shape
+                .iter()
+                .rev()
+                .fold(layout, |layout, &len| ElementLayout::array(len, layout));
+
+            if mode.aligned {
+                offset = checked_round_up(offset, align).ok_or_else(|| self.too_large())?;
+                struct_align = struct_align.max(align);
+            }
+            let name = self.parse_name()?;
+            let size = layout.checked_size().ok_or_else(|| self.too_large())?;
+            fields.push(Field {
+                name,
+                offset,
+                layout,
+            });
+            offset = offset.checked_add(size).ok_or_else(|| self.too_large())?;
+        }
+        if in_struct && mode.aligned {
+            offset = checked_round_up(offset, struct_align).ok_or_else(|| self.too_large())?;
+        }
+        Ok((fields, offset, struct_align))
+    }
+}
+
+/// Whether the repeat count of the format code is the length of a byte string.
+fn is_string_code(code: u8) -> bool {
+    code == b's' || code == b'p'
+}
+
+fn scalar_type(code: u8, native_size: bool) -> ElementType {
+    match code {
+        b'P' if native_size => ElementType::UnsignedInteger {
+            bytes: mem::size_of::<*mut raw::c_void>(),
+        },
+        code if native_size => super::native_element_type_from_type_char(code),
+        code => super::standard_element_type_from_type_char(code),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar(ty: ElementType, byte_order: ByteOrder) -> ElementLayout {
+        ElementLayout::Scalar { ty, byte_order }
+    }
+
+    fn field(name: Option<&str>, offset: usize, layout: ElementLayout) -> Field {
+        Field {
+            name: name.map(str::to_owned),
+            offset,
+            layout,
+        }
+    }
+
+    #[test]
+    fn test_parse_scalars() {
+        let native_i32 = scalar(ElementType::SignedInteger { bytes: 4 }, ByteOrder::NATIVE);
+        assert_eq!(ElementLayout::parse("i").unwrap(), native_i32);
+        assert_eq!(ElementLayout::parse("@i").unwrap(), native_i32);
+        assert_eq!(ElementLayout::parse("=i").unwrap(), native_i32);
+        assert_eq!(
+            ElementLayout::parse("!H").unwrap(),
+            scalar(
+                ElementType::UnsignedInteger { bytes: 2 },
+                ByteOrder::BigEndian
+            )
+        );
+        assert_eq!(
+            ElementLayout::parse("<e").unwrap(),
+            scalar(ElementType::Float { bytes: 2 }, ByteOrder::LittleEndian)
+        );
+        assert_eq!(
+            ElementLayout::parse("Zd").unwrap(),
+            scalar(ElementType::Complex { bytes: 16 }, ByteOrder::NATIVE)
+        );
+        assert_eq!(
+            ElementLayout::parse(">Zf").unwrap(),
+            scalar(ElementType::Complex { bytes: 8 }, ByteOrder::BigEndian)
+        );
+        assert_eq!(
+            ElementLayout::parse("?").unwrap(),
+            scalar(ElementType::Bool, ByteOrder::NATIVE)
+        );
+        assert_eq!(
+            ElementLayout::parse("<l").unwrap().size(),
+            4,
+            "standard sizes apply with a modifier"
+        );
+        assert_eq!(ElementLayout::parse("O").unwrap(), ElementLayout::Object);
+        assert_eq!(
+            ElementLayout::parse("10s").unwrap(),
+            ElementLayout::Bytes { len: 10 }
+        );
+    }
+
+    #[test]
+    fn test_parse_arrays() {
+        let layout = ElementLayout::parse("(2,3)<d").unwrap();
+        assert_eq!(
+            layout,
+            ElementLayout::Array {
+                shape: vec![2, 3],
+                element: Box::new(scalar(
+                    ElementType::Float { bytes: 8 },
+                    ByteOrder::LittleEndian
+                )),
+            }
+        );
+        assert_eq!(layout.size(), 48);
+        assert_eq!(ElementLayout::parse("3i").unwrap().size(), 12);
+        assert_eq!(ElementLayout::parse("( 2 , 2 )3B").unwrap().size(), 12);
+    }
+
+    #[test]
+    fn test_parse_structs() {
+        let double = scalar(ElementType::Float { bytes: 8 }, ByteOrder::LittleEndian);
+        let layout = ElementLayout::parse("T{<b:a:7x<d:b:(3)<h:c:}").unwrap();
+        assert_eq!(layout.size(), 22);
+        match &layout {
+            ElementLayout::Struct { fields, .. } => {
+                assert_eq!(fields.len(), 3);
+                assert_eq!(fields[1], field(Some("b"), 8, double));
+                assert_eq!(fields[2].offset, 16);
+                assert_eq!(fields[2].layout.size(), 6);
+            }
+            _ => panic!("expected a struct, got {:?}", layout),
+        }
+
+        // top-level items form a struct, aligned in native mode
+        let layout = ElementLayout::parse("bi").unwrap();
+        match &layout {
+            ElementLayout::Struct { fields, size } => {
+                assert_eq!(fields[1].offset, 4);
+                assert_eq!(*size, 8);
+            }
+            _ => panic!("expected a struct, got {:?}", layout),
+        }
+        assert_eq!(ElementLayout::parse("=bi").unwrap().size(), 5);
+        assert_eq!(ElementLayout::parse("^bi").unwrap().size(), 5);
+
+        // nested structs are padded to their alignment in native mode
+        let layout = ElementLayout::parse("T{T{db}:inner:b:last:}").unwrap();
+        assert_eq!(layout.size(), 24);
+        match &layout {
+            ElementLayout::Struct { fields, .. } => assert_eq!(fields[1].offset, 16),
+            _ => panic!("expected a struct, got {:?}", layout),
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for format in &[
+            "",
+            "T{i",
+            "(2,",
+            "(2)",
+            "Zi",
+            "g",
+            "i:name",
+            "T(",
+            "99999999999999999999999i",
+            "(4294967296,4294967296)d",
+            "18446744073709551615xi",
+            "T{(1152921504606846976)d(1152921504606846976)d}",
+        ] {
+            let err = ElementLayout::parse(format).unwrap_err();
+            crate::Python::with_gil(|py| {
+                assert!(err.is_instance::<PyValueError>(py), "{:?}", format)
+            });
+        }
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for format in &[
+            "B",
+            "Zd",
+            "(2,3)f",
+            "T{=i:x:4x=d:y:}",
+            "T{=?:flag:3xT{=f:re:=f:im:}:value:}",
+            "T{O:obj:4s:tag:4x}",
+        ] {
+            assert_eq!(ElementLayout::parse(format).unwrap().to_string(), *format);
+        }
+        assert_eq!(ElementLayout::parse("@I").unwrap().to_string(), "I");
+
+        // explicit byte orders are written as `=` when they match the native one
+        for format in &[">q", "<H", "T{<b:a:7x<d:b:(3)<h:c:}", "T{=i:x:4x>d:y:}"] {
+            let layout = ElementLayout::parse(format).unwrap();
+            assert_eq!(ElementLayout::parse(&layout.to_string()).unwrap(), layout);
+        }
+    }
+
+    #[test]
+    fn test_compatibility() {
+        let repr_c = ElementLayout::repr_c(
+            16,
+            vec![
+                ("x", ElementLayout::parse("i").unwrap(), 4),
+                ("y", ElementLayout::parse("d").unwrap(), 8),
+            ],
+        );
+        assert!(ElementLayout::parse("T{i:a:d:b:}")
+            .unwrap()
+            .is_compatible(&repr_c));
+        assert!(ElementLayout::parse("T{=i:x:4x=d:y:}")
+            .unwrap()
+            .is_compatible(&repr_c));
+        assert!(!ElementLayout::parse("T{=i:x:=d:y:}")
+            .unwrap()
+            .is_compatible(&repr_c));
+        assert!(!ElementLayout::parse("T{i:x:q:y:}")
+            .unwrap()
+            .is_compatible(&repr_c));
+        #[cfg(target_endian = "little")]
+        assert!(!ElementLayout::parse(">i")
+            .unwrap()
+            .is_compatible(&ElementLayout::parse("i").unwrap()));
+    }
+}
@@ -0,0 +1,672 @@
+//! N-dimensional views over the memory of a [`PyBuffer`].
+use super::{Element, PyBuffer, ReadOnlyCell};
+use crate::exceptions::{PyBufferError, PyIndexError, PyValueError};
+use crate::{PyResult, Python};
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::{fmt, mem, ptr, slice};
+
+impl<T: Element> PyBuffer<T> {
+    /// Gets an N-dimensional view of the buffer memory.
+    ///
+    /// Unlike [`as_slice`](PyBuffer::as_slice), this works for buffers of any layout, as the
+    /// view follows the shape, strides and suboffsets of the buffer.
+    pub fn as_view<'a>(&'a self, _py: Python<'a>) -> BufferView<'a, T> {
+        BufferView {
+            ptr: self.buf_ptr() as *mut u8,
+            shape: self.shape().to_vec(),
+            strides: self.strides().to_vec(),
+            offsets: vec![0; self.dimensions()],
+            suboffsets: self
+                .suboffsets()
+                .filter(|suboffsets| suboffsets.iter().any(|&suboffset| suboffset >= 0))
+                .map(<[isize]>::to_vec),
+            readonly: self.readonly(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// An N-dimensional view of the elements of a [`PyBuffer`], created by [`PyBuffer::as_view`].
+///
+/// The view honors the shape, strides and suboffsets of the buffer, so non-contiguous buffers,
+/// such as slices of NumPy arrays, can be read without copying them first. Rows, sub-slices and
+/// transpositions of a view are views of the same memory.
+///
+/// As with the slices returned by `PyBuffer`, any call into Python may modify the memory, so
+/// elements are copied out of the view rather than borrowed from it.
+///
+/// # Example
+///
+/// ```
+/// use pyo3::buffer::PyBuffer;
+/// use pyo3::Python;
+///
+/// Python::with_gil(|py| {
+///     let matrix = py
+///         .eval("memoryview(bytes(range(6))).cast('B', [2, 3])", None, None)
+///         .unwrap();
+///     let buffer = PyBuffer::<u8>::get(matrix).unwrap();
+///     let view = buffer.as_view(py);
+///     assert_eq!(view.shape(), [2, 3]);
+///     assert_eq!(view.get(&[1, 2]), Some(5));
+///
+///     let columns = view.transpose().unwrap();
+///     assert_eq!(columns.to_vec(), [0, 3, 1, 4, 2, 5]);
+///     let sums: Vec<u32> = columns
+///         .rows()
+///         .map(|column| column.to_vec().into_iter().map(u32::from).sum())
+///         .collect();
+///     assert_eq!(sums, [3, 5, 7]);
+/// });
+/// ```
+#[derive(Clone)]
+pub struct BufferView<'a, T: Element> {
+    ptr: *mut u8,
+    shape: Vec<usize>,
+    strides: Vec<isize>,
+    // bytes added along each axis by slicing, before the axis' suboffset is followed
+    offsets: Vec<isize>,
+    suboffsets: Option<Vec<isize>>,
+    readonly: bool,
+    _marker: PhantomData<&'a [ReadOnlyCell<T>]>,
+}
+
+impl<'a, T: Element> BufferView<'a, T> {
+    /// Gets the number of dimensions.
+    ///
+    /// May be 0 for a view of a single element.
+    #[inline]
+    pub fn ndim(&self) -> usize {
+        self.shape.len()
+    }
+
+    /// Gets the length of the view in each dimension.
+    #[inline]
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Gets the number of bytes between consecutive elements in each dimension.
+    ///
+    /// Strides can be zero or negative.
+    #[inline]
+    pub fn strides(&self) -> &[isize] {
+        &self.strides
+    }
+
+    /// Gets the suboffsets of the view, if the elements along any dimension are reached through
+    /// pointers. See [`PyBuffer::suboffsets`].
+    #[inline]
+    pub fn suboffsets(&self) -> Option<&[isize]> {
+        self.suboffsets.as_deref()
+    }
+
+    /// Gets the total number of elements.
+    pub fn len(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Returns `true` if the view has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets whether the underlying buffer is read-only.
+    #[inline]
+    pub fn readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Returns a copy of the element at `index`, or `None` if the index does not have one entry
+    /// per dimension or is out of bounds.
+    pub fn get(&self, index: &[usize]) -> Option<T> {
+        self.element_ptr(index)
+            .map(|ptr| unsafe { ptr::read_unaligned(ptr) })
+    }
+
+    /// Sets the element at `index`.
+    ///
+    /// Returns `BufferError` if the buffer is read-only, and `IndexError` if the index does not
+    /// have one entry per dimension or is out of bounds.
+    pub fn set(&self, index: &[usize], value: T) -> PyResult<()> {
+        if self.readonly {
+            return Err(PyBufferError::new_err("buffer is read-only"));
+        }
+        let ptr = self
+            .element_ptr(index)
+            .ok_or_else(|| PyIndexError::new_err("buffer index out of range"))?;
+        unsafe { ptr::write_unaligned(ptr, value) };
+        Ok(())
+    }
+
+    /// Returns the view of the sub-array at `index` along the first dimension, or `None` if the
+    /// view is zero-dimensional or the index is out of bounds.
+    pub fn row(&self, index: usize) -> Option<BufferView<'a, T>> {
+        if index >= self.shape.first().copied().unwrap_or(0) {
+            return None;
+        }
+        Some(BufferView {
+            ptr: unsafe { self.follow(&[index]) },
+            shape: self.shape[1..].to_vec(),
+            strides: self.strides[1..].to_vec(),
+            offsets: self.offsets[1..].to_vec(),
+            suboffsets: self
+                .suboffsets
+                .as_ref()
+                .map(|suboffsets| suboffsets[1..].to_vec())
+                .filter(|suboffsets| suboffsets.iter().any(|&suboffset| suboffset >= 0)),
+            readonly: self.readonly,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns an iterator over the sub-arrays along the first dimension.
+    ///
+    /// The iterator is empty for a zero-dimensional view.
+    pub fn rows(&self) -> Rows<'a, T> {
+        Rows {
+            range: 0..self.shape.first().copied().unwrap_or(0),
+            view: self.clone(),
+        }
+    }
+
+    /// Restricts the view to the indices in `range` along `axis`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is not a dimension of the view, or if `range` is decreasing or extends
+    /// past the length of the axis.
+    pub fn slice_axis(&self, axis: usize, range: Range<usize>) -> BufferView<'a, T> {
+        assert!(
+            axis < self.ndim(),
+            "axis {} is out of bounds for a view with {} dimensions",
+            axis,
+            self.ndim()
+        );
+        assert!(
+            range.start <= range.end && range.end <= self.shape[axis],
+            "range {:?} is out of bounds for an axis of length {}",
+            range,
+            self.shape[axis]
+        );
+        let mut view = self.clone();
+        view.offsets[axis] += self.strides[axis] * range.start as isize;
+        view.shape[axis] = range.end - range.start;
+        view
+    }
+
+    /// Returns the view with its axes reversed, so that for a two-dimensional view, rows become
+    /// columns.
+    ///
+    /// Returns `BufferError` if the view has suboffsets.
+    pub fn transpose(&self) -> PyResult<BufferView<'a, T>> {
+        let axes: Vec<usize> = (0..self.ndim()).rev().collect();
+        self.permute_axes(&axes)
+    }
+
+    /// Returns the view with its axes reordered, so that axis `i` of the result is axis
+    /// `axes[i]` of this view.
+    ///
+    /// Returns `ValueError` if `axes` is not a permutation of the axes of the view, and
+    /// `BufferError` if the view has suboffsets, which must be followed in their original order.
+    pub fn permute_axes(&self, axes: &[usize]) -> PyResult<BufferView<'a, T>> {
+        let mut seen = vec![false; self.ndim()];
+        let is_permutation = axes.len() == self.ndim()
+            && axes
+                .iter()
+                .all(|&axis| axis < seen.len() && !mem::replace(&mut seen[axis], true));
+        if !is_permutation {
+            return Err(PyValueError::new_err(format!(
+                "{:?} is not a permutation of the axes of a view with {} dimensions",
+                axes,
+                self.ndim()
+            )));
+        }
+        if self.suboffsets.is_some() {
+            return Err(PyBufferError::new_err(
+                "cannot reorder the axes of a buffer with suboffsets",
+            ));
+        }
+        let mut view = self.clone();
+        for (i, &axis) in axes.iter().enumerate() {
+            view.shape[i] = self.shape[axis];
+            view.strides[i] = self.strides[axis];
+            view.offsets[i] = self.offsets[axis];
+        }
+        Ok(view)
+    }
+
+    /// Gets whether the elements are stored contiguously in C-style order (last index varies
+    /// fastest).
+    pub fn is_c_contiguous(&self) -> bool {
+        if self.suboffsets.is_some() {
+            return false;
+        }
+        if self.is_empty() {
+            return true;
+        }
+        let mut expected = mem::size_of::<T>() as isize;
+        for (&len, &stride) in self.shape.iter().zip(&self.strides).rev() {
+            if len != 1 && stride != expected {
+                return false;
+            }
+            expected *= len as isize;
+        }
+        true
+    }
+
+    /// Gets the elements as a slice, if they are stored contiguously in C-style order and are
+    /// aligned for `T`.
+    pub fn as_slice(&self) -> Option<&'a [ReadOnlyCell<T>]> {
+        if !self.is_c_contiguous() {
+            None
+        } else if self.is_empty() {
+            Some(&[])
+        } else if !self.is_aligned() {
+            // e.g. a single field of a packed struct, whose stride `is_c_contiguous` ignores
+            None
+        } else {
+            unsafe {
+                Some(slice::from_raw_parts(
+                    self.base_ptr() as *const ReadOnlyCell<T>,
+                    self.len(),
+                ))
+            }
+        }
+    }
+
+    /// Copies the elements into a new `Vec`, in C-style order.
+    pub fn to_vec(&self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.len());
+        self.extend_vec(&mut vec);
+        vec
+    }
+
+    fn extend_vec(&self, vec: &mut Vec<T>) {
+        if let Some(slice) = self.as_slice() {
+            vec.extend(slice.iter().map(ReadOnlyCell::get));
+        } else if self.ndim() == 0 {
+            vec.push(unsafe { ptr::read_unaligned(self.ptr as *const T) });
+        } else {
+            for row in self.rows() {
+                row.extend_vec(vec);
+            }
+        }
+    }
+
+    /// Gets whether the first element and the strides are aligned for `T`, which a view without
+    /// suboffsets needs to be borrowed rather than read element by element.
+    fn is_aligned(&self) -> bool {
+        let align = mem::align_of::<T>();
+        self.base_ptr().align_offset(align) == 0
+            && self
+                .strides
+                .iter()
+                .all(|&stride| stride % align as isize == 0)
+    }
+
+    fn element_ptr(&self, index: &[usize]) -> Option<*mut T> {
+        if index.len() != self.ndim() || index.iter().zip(&self.shape).any(|(&i, &len)| i >= len) {
+            return None;
+        }
+        Some(unsafe { self.follow(index) } as *mut T)
+    }
+
+    /// Gets the address selected by `index` along the leading dimensions.
+    ///
+    /// The index must be in bounds.
+    unsafe fn follow(&self, index: &[usize]) -> *mut u8 {
+        let mut ptr = self.ptr;
+        for (axis, &i) in index.iter().enumerate() {
+            ptr = ptr.offset(self.offsets[axis] + self.strides[axis] * i as isize);
+            if let Some(suboffset) = self.suboffset(axis) {
+                ptr = (*(ptr as *const *mut u8)).offset(suboffset);
+            }
+        }
+        ptr
+    }
+
+    fn suboffset(&self, axis: usize) -> Option<isize> {
+        self.suboffsets
+            .as_ref()
+            .map(|suboffsets| suboffsets[axis])
+            .filter(|&suboffset| suboffset >= 0)
+    }
+
+    /// Gets the address of the first element of a view without suboffsets.
+    fn base_ptr(&self) -> *mut u8 {
+        debug_assert!(self.suboffsets.is_none());
+        self.ptr.wrapping_offset(self.offsets.iter().sum())
+    }
+}
+
+impl<T: Element> fmt::Debug for BufferView<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BufferView")
+            .field("shape", &self.shape)
+            .field("strides", &self.strides)
+            .field("suboffsets", &self.suboffsets)
+            .field("readonly", &self.readonly)
+            .finish()
+    }
+}
+
+/// Iterator over the sub-arrays of a [`BufferView`] along its first dimension, created by
+/// [`BufferView::rows`].
+#[derive(Clone, Debug)]
+pub struct Rows<'a, T: Element> {
+    view: BufferView<'a, T>,
+    range: Range<usize>,
+}
+
+impl<'a, T: Element> Iterator for Rows<'a, T> {
+    type Item = BufferView<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.range.next()?;
+        self.view.row(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<T: Element> DoubleEndedIterator for Rows<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.range.next_back()?;
+        self.view.row(index)
+    }
+}
+
+impl<T: Element> ExactSizeIterator for Rows<'_, T> {}
+
+#[cfg(feature = "ndarray")]
+impl<'a, T: Element> BufferView<'a, T> {
+    /// Gets an [`ndarray::ArrayView`] of the elements, without copying them.
+    ///
+    /// Returns `BufferError` if the view has suboffsets, if its strides are not multiples of
+    /// the element size, or if its first element is not aligned for `T`.
+    ///
+    /// # Safety
+    ///
+    /// The array borrows the buffer memory directly, so the caller must make sure that the
+    /// memory is not modified, by Python code or through another view, while the array is alive.
+    pub unsafe fn as_array(&self) -> PyResult<ndarray::ArrayViewD<'a, T>> {
+        let (ptr, shape, inverted) = self.ndarray_shape()?;
+        let mut array = ndarray::ArrayViewD::from_shape_ptr(shape, ptr as *const T);
+        for axis in inverted {
+            array.invert_axis(ndarray::Axis(axis));
+        }
+        Ok(array)
+    }
+
+    /// Gets an [`ndarray::ArrayViewMut`] of the elements, without copying them.
+    ///
+    /// Returns `BufferError` if the buffer is read-only, if the view has suboffsets, if its
+    /// strides are not multiples of the element size, or if its first element is not aligned
+    /// for `T`.
+    ///
+    /// # Safety
+    ///
+    /// The array borrows the buffer memory mutably, so the caller must make sure that the
+    /// memory is not accessed, by Python code or through another view, while the array is alive.
+    /// The elements of the view must not overlap, as they may for zero strides.
+    pub unsafe fn as_array_mut(&self) -> PyResult<ndarray::ArrayViewMutD<'a, T>> {
+        if self.readonly {
+            return Err(PyBufferError::new_err("buffer is read-only"));
+        }
+        let (ptr, shape, inverted) = self.ndarray_shape()?;
+        let mut array = ndarray::ArrayViewMutD::from_shape_ptr(shape, ptr as *mut T);
+        for axis in inverted {
+            array.invert_axis(ndarray::Axis(axis));
+        }
+        Ok(array)
+    }
+
+    /// Converts the layout to non-negative strides in elements, which `ndarray` requires,
+    /// returning the axes which need to be inverted afterwards.
+    fn ndarray_shape(
+        &self,
+    ) -> PyResult<(*mut u8, ndarray::StrideShape<ndarray::IxDyn>, Vec<usize>)> {
+        use ndarray::ShapeBuilder;
+
+        if self.suboffsets.is_some() {
+            return Err(PyBufferError::new_err(
+                "cannot create an ndarray view of a buffer with suboffsets",
+            ));
+        }
+        if !self.is_aligned() {
+            return Err(PyBufferError::new_err(
+                "buffer elements are not aligned for the element type",
+            ));
+        }
+        let item_size = mem::size_of::<T>() as isize;
+        let mut ptr = self.base_ptr();
+        let mut strides = Vec::with_capacity(self.ndim());
+        let mut inverted = Vec::new();
+        for (axis, (&len, &stride)) in self.shape.iter().zip(&self.strides).enumerate() {
+            if stride % item_size != 0 {
+                return Err(PyBufferError::new_err(
+                    "buffer strides are not multiples of the element size",
+                ));
+            }
+            if stride < 0 {
+                // start from the element with the lowest address instead
+                if len > 0 {
+                    ptr = ptr.wrapping_offset(stride * (len as isize - 1));
+                }
+                inverted.push(axis);
+            }
+            strides.push((stride.abs() / item_size) as usize);
+        }
+        let shape = ndarray::IxDyn(&self.shape).strides(ndarray::IxDyn(&strides));
+        Ok((ptr, shape, inverted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exceptions::PyBufferError;
+
+    #[test]
+    fn test_contiguous_view() {
+        Python::with_gil(|py| {
+            let matrix = py
+                .eval(
+                    "memoryview(__import__('array').array('i', range(12))).cast('B').cast('i', [3, 4])",
+                    None,
+                    None,
+                )
+                .unwrap();
+            let buffer = PyBuffer::<i32>::get(matrix).unwrap();
+            let view = buffer.as_view(py);
+            assert_eq!(view.ndim(), 2);
+            assert_eq!(view.shape(), [3, 4]);
+            assert_eq!(view.strides(), [16, 4]);
+            assert_eq!(view.len(), 12);
+            assert!(view.is_c_contiguous());
+            assert_eq!(view.as_slice().unwrap().len(), 12);
+
+            assert_eq!(view.get(&[2, 1]), Some(9));
+            assert_eq!(view.get(&[3, 0]), None);
+            assert_eq!(view.get(&[0]), None);
+
+            let row = view.row(1).unwrap();
+            assert_eq!(row.to_vec(), [4, 5, 6, 7]);
+            assert_eq!(row.row(3).unwrap().get(&[]), Some(7));
+            assert!(view.row(3).is_none());
+            assert_eq!(view.rows().len(), 3);
+            let firsts: Vec<i32> = view
+                .rows()
+                .rev()
+                .map(|row| row.get(&[0]).unwrap())
+                .collect();
+            assert_eq!(firsts, [8, 4, 0]);
+
+            let block = view.slice_axis(0, 1..3).slice_axis(1, 1..3);
+            assert_eq!(block.shape(), [2, 2]);
+            assert!(!block.is_c_contiguous());
+            assert!(block.as_slice().is_none());
+            assert_eq!(block.to_vec(), [5, 6, 9, 10]);
+
+            let transposed = view.transpose().unwrap();
+            assert_eq!(transposed.shape(), [4, 3]);
+            assert_eq!(transposed.strides(), [4, 16]);
+            assert_eq!(transposed.get(&[1, 2]), Some(9));
+            assert_eq!(transposed.row(0).unwrap().to_vec(), [0, 4, 8]);
+            assert_eq!(
+                block.transpose().unwrap().to_vec(),
+                [5, 9, 6, 10],
+                "transposition keeps slicing"
+            );
+
+            let err = view.permute_axes(&[0, 0]).unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_negative_strides() {
+        Python::with_gil(|py| {
+            let matrix = py
+                .eval(
+                    "memoryview(__import__('array').array('i', range(6))).cast('B').cast('i', [3, 2])[::-1]",
+                    None,
+                    None,
+                )
+                .unwrap();
+            let buffer = PyBuffer::<i32>::get(matrix).unwrap();
+            let view = buffer.as_view(py);
+            assert_eq!(view.strides(), [-8, 4]);
+            assert!(!view.is_c_contiguous());
+            assert_eq!(view.to_vec(), [4, 5, 2, 3, 0, 1]);
+            assert_eq!(view.slice_axis(0, 1..3).to_vec(), [2, 3, 0, 1]);
+            assert_eq!(view.transpose().unwrap().to_vec(), [4, 2, 0, 5, 3, 1]);
+        });
+    }
+
+    #[test]
+    fn test_set() {
+        Python::with_gil(|py| {
+            let locals = crate::types::PyDict::new(py);
+            py.run(
+                "data = __import__('array').array('i', range(6))\nview = memoryview(data)[::2]",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+            let buffer = PyBuffer::<i32>::get(locals.get_item("view").unwrap()).unwrap();
+            let view = buffer.as_view(py);
+            assert_eq!(view.to_vec(), [0, 2, 4]);
+            view.set(&[1], 20).unwrap();
+            let err = view.set(&[3], 0).unwrap_err();
+            assert!(err.is_instance::<PyIndexError>(py));
+            crate::py_run!(py, *locals, "assert list(data) == [0, 1, 20, 3, 4, 5]");
+            buffer.release(py);
+
+            let bytes = py.eval("b'abc'", None, None).unwrap();
+            let buffer = PyBuffer::<u8>::get(bytes).unwrap();
+            let err = buffer.as_view(py).set(&[0], b'x').unwrap_err();
+            assert!(err.is_instance::<PyBufferError>(py));
+        });
+    }
+
+    #[test]
+    fn test_suboffsets() {
+        // a PIL-style image: an array of pointers to separately allocated rows
+        let rows = [[1i32, 2, 3], [4, 5, 6]];
+        let pointers = [rows[1].as_ptr(), rows[0].as_ptr()];
+        let view = BufferView::<i32> {
+            ptr: pointers.as_ptr() as *mut u8,
+            shape: vec![2, 3],
+            strides: vec![mem::size_of::<*const i32>() as isize, 4],
+            offsets: vec![0, 0],
+            suboffsets: Some(vec![0, -1]),
+            readonly: true,
+            _marker: PhantomData,
+        };
+        assert_eq!(view.get(&[0, 2]), Some(6));
+        assert_eq!(view.to_vec(), [4, 5, 6, 1, 2, 3]);
+        assert!(!view.is_c_contiguous());
+
+        let row = view.row(1).unwrap();
+        assert!(row.suboffsets().is_none());
+        assert!(row.is_c_contiguous());
+        assert_eq!(row.as_slice().unwrap()[2].get(), 3);
+
+        let sliced = view.slice_axis(1, 1..3).slice_axis(0, 1..2);
+        assert_eq!(sliced.to_vec(), [2, 3]);
+
+        Python::with_gil(|py| {
+            let err = view.transpose().unwrap_err();
+            assert!(err.is_instance::<PyBufferError>(py));
+        });
+    }
+
+    #[test]
+    fn test_unaligned_field() {
+        // the `i32` field of three packed records of an `i32` and a `u8`
+        let mut data = [0u32; 4];
+        let bytes = data.as_mut_ptr() as *mut u8;
+        for i in 0..3 {
+            unsafe { ptr::write_unaligned(bytes.add(5 * i) as *mut i32, i as i32 + 1) };
+        }
+        let view = BufferView::<i32> {
+            ptr: bytes,
+            shape: vec![3],
+            strides: vec![5],
+            offsets: vec![0],
+            suboffsets: None,
+            readonly: true,
+            _marker: PhantomData,
+        };
+        assert_eq!(view.to_vec(), [1, 2, 3]);
+
+        let second = view.slice_axis(0, 1..2);
+        assert!(second.is_c_contiguous());
+        assert!(second.as_slice().is_none());
+        assert_eq!(second.to_vec(), [2]);
+        assert_eq!(second.get(&[0]), Some(2));
+
+        #[cfg(feature = "ndarray")]
+        Python::with_gil(|py| {
+            let err = unsafe { second.as_array() }.unwrap_err();
+            assert!(err.is_instance::<PyBufferError>(py));
+        });
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_ndarray() {
+        Python::with_gil(|py| {
+            let locals = crate::types::PyDict::new(py);
+            py.run(
+                "data = __import__('array').array('i', range(12))\nview = memoryview(data).cast('B').cast('i', [3, 4])[::-2]",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+            let buffer = PyBuffer::<i32>::get(locals.get_item("view").unwrap()).unwrap();
+            let view = buffer.as_view(py);
+            let array = unsafe { view.as_array() }.unwrap();
+            assert_eq!(
+                array,
+                ndarray::arr2(&[[8, 9, 10, 11], [0, 1, 2, 3]]).into_dyn()
+            );
+            let array = unsafe { view.transpose().unwrap().as_array() }.unwrap();
+            assert_eq!(array.shape(), [4, 2]);
+            assert_eq!(array[[1, 0]], 9);
+
+            let mut array = unsafe { view.slice_axis(1, 1..3).as_array_mut() }.unwrap();
+            array.fill(-1);
+            crate::py_run!(
+                py,
+                *locals,
+                "assert list(data) == [0, -1, -1, 3, 4, 5, 6, 7, 8, -1, -1, 11]"
+            );
+        });
+    }
+}
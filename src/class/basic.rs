@@ -9,7 +9,12 @@
 //! [typeobj docs](https://docs.python.org/3/c-api/typeobj.html)
 
 use crate::callback::{HashCallbackOutput, IntoPyCallbackOutput};
-use crate::{exceptions, ffi, FromPyObject, PyAny, PyCell, PyClass, PyObject};
+use crate::{
+    exceptions, ffi, AsPyPointer, FromPyObject, IntoPy, PyAny, PyCell, PyClass, PyErr,
+    PyNativeType, PyObject, PyResult,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::os::raw::c_int;
 
 /// Operators for the `__richcmp__` method
@@ -29,6 +34,22 @@ pub enum CompareOp {
     Ge = ffi::Py_GE as isize,
 }
 
+impl CompareOp {
+    fn from_raw(op: c_int) -> PyResult<Self> {
+        match op {
+            ffi::Py_LT => Ok(CompareOp::Lt),
+            ffi::Py_LE => Ok(CompareOp::Le),
+            ffi::Py_EQ => Ok(CompareOp::Eq),
+            ffi::Py_NE => Ok(CompareOp::Ne),
+            ffi::Py_GT => Ok(CompareOp::Gt),
+            ffi::Py_GE => Ok(CompareOp::Ge),
+            _ => Err(exceptions::PyValueError::new_err(
+                "tp_richcompare called with invalid comparison operator",
+            )),
+        }
+    }
+}
+
 /// Basic Python class customization
 #[allow(unused_variables)]
 pub trait PyObjectProtocol<'p>: PyClass {
@@ -188,24 +209,112 @@ where
     crate::callback_body!(py, {
         let slf = py.from_borrowed_ptr::<crate::PyCell<T>>(slf);
         let arg = extract_or_return_not_implemented!(py, arg);
-        let op = match op {
-            ffi::Py_LT => CompareOp::Lt,
-            ffi::Py_LE => CompareOp::Le,
-            ffi::Py_EQ => CompareOp::Eq,
-            ffi::Py_NE => CompareOp::Ne,
-            ffi::Py_GT => CompareOp::Gt,
-            ffi::Py_GE => CompareOp::Ge,
-            _ => {
-                return Err(exceptions::PyValueError::new_err(
-                    "tp_richcompare called with invalid comparison operator",
-                ))
-            }
-        };
+        let op = CompareOp::from_raw(op)?;
 
         slf.try_borrow()?.__richcmp__(arg, op).convert(py)
     })
 }
 
+/// Implementation of `tp_richcompare` for `#[pyclass(eq)]`, using `PartialEq`.
+///
+/// Only `==` and `!=` are supported; all other comparisons, as well as comparisons against
+/// objects which are not instances of `T`, return `NotImplemented`.
+#[doc(hidden)]
+pub unsafe extern "C" fn richcmp_from_eq<T>(
+    slf: *mut ffi::PyObject,
+    arg: *mut ffi::PyObject,
+    op: c_int,
+) -> *mut ffi::PyObject
+where
+    T: PyClass + PartialEq,
+{
+    crate::callback_body!(py, {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let arg: &PyCell<T> = extract_or_return_not_implemented!(py, arg);
+        let (slf, arg) = (slf.try_borrow()?, arg.try_borrow()?);
+        let result = match CompareOp::from_raw(op)? {
+            CompareOp::Eq => (*slf == *arg).into_py(py),
+            CompareOp::Ne => (*slf != *arg).into_py(py),
+            _ => py.NotImplemented(),
+        };
+        PyResult::Ok(result)
+    })
+}
+
+/// Implementation of `tp_richcompare` for `#[pyclass(eq, ord)]`, using `PartialEq` and
+/// `PartialOrd`.
+///
+/// Comparisons against objects which are not instances of `T` return `NotImplemented`.
+#[doc(hidden)]
+pub unsafe extern "C" fn richcmp_from_ord<T>(
+    slf: *mut ffi::PyObject,
+    arg: *mut ffi::PyObject,
+    op: c_int,
+) -> *mut ffi::PyObject
+where
+    T: PyClass + PartialOrd,
+{
+    crate::callback_body!(py, {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let arg: &PyCell<T> = extract_or_return_not_implemented!(py, arg);
+        let (slf, arg) = (slf.try_borrow()?, arg.try_borrow()?);
+        let result = match CompareOp::from_raw(op)? {
+            CompareOp::Lt => *slf < *arg,
+            CompareOp::Le => *slf <= *arg,
+            CompareOp::Eq => *slf == *arg,
+            CompareOp::Ne => *slf != *arg,
+            CompareOp::Gt => *slf > *arg,
+            CompareOp::Ge => *slf >= *arg,
+        };
+        PyResult::Ok(result.into_py(py))
+    })
+}
+
+/// Formats the `__repr__` for `#[pyclass(repr)]` in the style of a dataclass, e.g.
+/// `Point(x=1, y=2)`, using the Python `repr` of each field.
+#[doc(hidden)]
+pub fn dataclass_repr(slf: &PyAny, fields: &[(&str, PyObject)]) -> PyResult<String> {
+    let py = slf.py();
+    // Guard against infinite recursion for objects which contain themselves, in the same way
+    // as `reprlib.recursive_repr` used by `dataclasses`.
+    match unsafe { ffi::Py_ReprEnter(slf.as_ptr()) } {
+        0 => (),
+        n if n > 0 => return Ok("...".to_owned()),
+        _ => return Err(PyErr::api_call_failed(py)),
+    }
+
+    let result = (|| {
+        let mut repr = format!("{}(", slf.get_type().name()?);
+        for (i, (name, value)) in fields.iter().enumerate() {
+            if i > 0 {
+                repr.push_str(", ");
+            }
+            repr.push_str(name);
+            repr.push('=');
+            repr.push_str(value.as_ref(py).repr()?.to_str()?);
+        }
+        repr.push(')');
+        Ok(repr)
+    })();
+
+    unsafe { ffi::Py_ReprLeave(slf.as_ptr()) };
+    result
+}
+
+/// Implementation of `tp_hash` for `#[pyclass(hash)]`, using `Hash`.
+#[doc(hidden)]
+pub unsafe extern "C" fn hash_from_hash<T>(slf: *mut ffi::PyObject) -> ffi::Py_hash_t
+where
+    T: PyClass + Hash,
+{
+    crate::callback_body!(py, {
+        let slf = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let mut hasher = DefaultHasher::new();
+        slf.try_borrow()?.hash(&mut hasher);
+        IntoPyCallbackOutput::<HashCallbackOutput>::convert(hasher.finish(), py)
+    })
+}
+
 py_func_set!(setattr, PyObjectSetAttrProtocol, T::__setattr__);
 py_func_del!(delattr, PyObjectDelAttrProtocol, T::__delattr__);
 py_func_set_del!(
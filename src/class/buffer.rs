@@ -4,9 +4,14 @@
 //!
 //! For more information check [buffer protocol](https://docs.python.org/3/c-api/buffer.html)
 //! c-api
+use crate::buffer::Element;
 use crate::callback::IntoPyCallbackOutput;
-use crate::{ffi, PyCell, PyClass, PyRefMut};
-use std::os::raw::c_int;
+use crate::exceptions::PyBufferError;
+use crate::pycell::{BorrowFlag, PyCellLayout};
+use crate::{ffi, AsPyPointer, PyCell, PyClass, PyErr, PyRefMut, PyResult};
+use std::ffi::CString;
+use std::os::raw::{c_int, c_void};
+use std::{mem, ptr};
 
 /// Buffer protocol interface
 ///
@@ -33,6 +38,333 @@ pub trait PyBufferReleaseBufferProtocol<'p>: PyBufferProtocol<'p> {
     type Result: IntoPyCallbackOutput<()>;
 }
 
+/// Exports the contents of a `#[pyclass]` through the buffer protocol.
+///
+/// This is a safe alternative to implementing [`PyBufferProtocol`]: PyO3 fills in the
+/// `Py_buffer`, derives its format from the [`Element`] type and keeps the object alive for as
+/// long as the buffer is in use.
+///
+/// Exported buffers are tracked like borrows of the object. While a read-only buffer is exported,
+/// `&mut self` methods fail with `BufferError`. A writable buffer may be modified by Python at any
+/// time, so while one is exported `&self` methods and further exports fail with `BufferError`
+/// too.
+///
+/// # Examples
+///
+/// ```
+/// use pyo3::class::buffer::BufferExport;
+/// use pyo3::prelude::*;
+///
+/// #[pyclass]
+/// struct Matrix {
+///     data: Vec<f64>,
+///     rows: usize,
+///     columns: usize,
+/// }
+///
+/// impl BufferExport for Matrix {
+///     type Item = f64;
+///
+///     fn buffer(&self) -> &[f64] {
+///         &self.data
+///     }
+///
+///     fn buffer_mut(&mut self) -> Option<&mut [f64]> {
+///         Some(&mut self.data)
+///     }
+///
+///     fn shape(&self) -> Option<Vec<usize>> {
+///         Some(vec![self.rows, self.columns])
+///     }
+/// }
+///
+/// Python::with_gil(|py| {
+///     let matrix = PyCell::new(py, Matrix { data: vec![0.0; 6], rows: 2, columns: 3 }).unwrap();
+///     pyo3::py_run!(py, matrix, r#"
+///         view = memoryview(matrix)
+///         assert view.format == 'd' and view.shape == (2, 3)
+///         view[1, 2] = 1.5
+///         view.release()
+///     "#);
+///     assert_eq!(matrix.borrow().data[5], 1.5);
+/// });
+/// ```
+pub trait BufferExport: PyClass {
+    /// The type of the exported elements, which must have a [`layout`](Element::layout) to
+    /// describe the buffer format; exports fail with `BufferError` otherwise.
+    type Item: Element;
+
+    /// Returns the elements to export as a read-only buffer.
+    fn buffer(&self) -> &[Self::Item];
+
+    /// Returns the elements to export as a writable buffer, or `None` if the buffer is read-only.
+    ///
+    /// This is only called when neither Rust code nor another buffer borrows the object.
+    /// Otherwise, and when this returns `None`, requests for a writable buffer fail and
+    /// [`buffer`](BufferExport::buffer) is used.
+    fn buffer_mut(&mut self) -> Option<&mut [Self::Item]> {
+        None
+    }
+
+    /// Returns the shape of the buffer, which defaults to a single dimension holding all
+    /// elements.
+    fn shape(&self) -> Option<Vec<usize>> {
+        None
+    }
+
+    /// Returns the strides of the buffer in bytes, which default to the strides of a C-contiguous
+    /// array of the given shape.
+    ///
+    /// Every element addressed by the shape and strides must lie within the exported slice;
+    /// negative strides are measured from the element at index zero of each dimension.
+    fn strides(&self) -> Option<Vec<isize>> {
+        None
+    }
+}
+
+/// Layout of an exported buffer, owned by the `Py_buffer` through its `internal` field.
+struct ExportedLayout {
+    format: CString,
+    shape: Vec<ffi::Py_ssize_t>,
+    strides: Vec<ffi::Py_ssize_t>,
+    writable: bool,
+}
+
+/// Restores the borrow flag of a cell which was lent out for exporting a buffer.
+struct RestoreFlag<'a, T: PyClass>(&'a PyCell<T>, BorrowFlag);
+
+impl<T: PyClass> Drop for RestoreFlag<'_, T> {
+    fn drop(&mut self) {
+        self.0.set_borrow_flag(self.1)
+    }
+}
+
+/// Checks that all elements addressed by `shape` and `strides` lie within `len` elements.
+///
+/// Returns the byte offset of the element at index zero, and the strides to use.
+fn check_layout(
+    len: usize,
+    item_size: usize,
+    shape: &[usize],
+    strides: Option<Vec<isize>>,
+) -> PyResult<(usize, Vec<isize>)> {
+    if shape.len() > ffi::PyBUF_MAX_NDIM as usize {
+        return Err(PyBufferError::new_err(format!(
+            "buffer has {} dimensions, but at most {} are supported",
+            shape.len(),
+            ffi::PyBUF_MAX_NDIM
+        )));
+    }
+    let strides = match strides {
+        Some(strides) if strides.len() != shape.len() => {
+            return Err(PyBufferError::new_err(format!(
+                "buffer has {} dimensions, but {} strides",
+                shape.len(),
+                strides.len()
+            )));
+        }
+        Some(strides) => strides,
+        None => {
+            let count = shape.iter().try_fold(1usize, |n, &dim| n.checked_mul(dim));
+            if count != Some(len) {
+                return Err(PyBufferError::new_err(format!(
+                    "buffer shape {:?} does not match its {} elements",
+                    shape, len
+                )));
+            }
+            return Ok((0, contiguous_strides(shape, item_size, false)));
+        }
+    };
+    if shape.contains(&0) {
+        return Ok((0, strides));
+    }
+    // the lowest and highest byte offsets of an element, relative to the element at index zero
+    let (mut min, mut max) = (0i128, 0i128);
+    for (&dim, &stride) in shape.iter().zip(&strides) {
+        let offset = (dim as i128 - 1) * stride as i128;
+        if offset < 0 {
+            min += offset;
+        } else {
+            max += offset;
+        }
+    }
+    if max - min + item_size as i128 > (len * item_size) as i128 {
+        return Err(PyBufferError::new_err(format!(
+            "buffer shape {:?} and strides {:?} exceed its {} elements",
+            shape, strides, len
+        )));
+    }
+    Ok((-min as usize, strides))
+}
+
+/// Computes the strides of a contiguous array in C or Fortran order.
+fn contiguous_strides(shape: &[usize], item_size: usize, fortran: bool) -> Vec<isize> {
+    let mut strides = vec![0; shape.len()];
+    let mut stride = item_size as isize;
+    for i in 0..shape.len() {
+        let i = if fortran { i } else { shape.len() - 1 - i };
+        strides[i] = stride;
+        stride = stride.wrapping_mul(shape[i] as isize);
+    }
+    strides
+}
+
+/// Whether `strides` equal `expected`, ignoring dimensions of length one.
+fn same_strides(shape: &[usize], strides: &[isize], expected: &[isize]) -> bool {
+    shape
+        .iter()
+        .zip(strides.iter().zip(expected))
+        .all(|(&dim, (stride, expected))| dim <= 1 || stride == expected)
+}
+
+unsafe fn export_buffer<T: BufferExport>(
+    cell: &PyCell<T>,
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+) -> PyResult<()> {
+    if view.is_null() {
+        return Err(PyBufferError::new_err("View is null"));
+    }
+    let flag = cell.get_borrow_flag();
+    if flag == BorrowFlag::HAS_MUTABLE_BORROW {
+        return Err(PyBufferError::new_err(
+            "cannot export a buffer while the object is mutably borrowed",
+        ));
+    }
+    // A writable export holds on to the slice returned by `buffer_mut`, which calling it again
+    // could invalidate, so it is exclusive like a mutable borrow.
+    if flag.has_writable_exports() {
+        return Err(PyBufferError::new_err(
+            "cannot export a buffer while a writable buffer is exported",
+        ));
+    }
+
+    let (buf, len, writable, shape, strides) = {
+        let _restore = RestoreFlag(cell, flag);
+        // Nothing may mutate the value while its buffer is being collected.
+        cell.set_borrow_flag(BorrowFlag::HAS_MUTABLE_BORROW);
+        let (shape, strides) = {
+            let value = &*cell.get_ptr();
+            (value.shape(), value.strides())
+        };
+        // Unless other Rust code or a buffer refers to the value, it can be lent out mutably.
+        let exported = if flag == BorrowFlag::UNUSED {
+            (*cell.get_ptr())
+                .buffer_mut()
+                .map(|slice| (slice.as_mut_ptr(), slice.len(), true))
+        } else {
+            None
+        };
+        let (buf, len, writable) = match exported {
+            Some(exported) => exported,
+            None => {
+                let slice = (*cell.get_ptr()).buffer();
+                (slice.as_ptr() as *mut T::Item, slice.len(), false)
+            }
+        };
+        (buf, len, writable, shape, strides)
+    };
+
+    if !writable && flags & ffi::PyBUF_WRITABLE == ffi::PyBUF_WRITABLE {
+        return Err(PyBufferError::new_err(if flag == BorrowFlag::UNUSED {
+            "Object is not writable"
+        } else {
+            "cannot export a writable buffer while the object is borrowed"
+        }));
+    }
+
+    let item_size = mem::size_of::<T::Item>();
+    let shape = shape.unwrap_or_else(|| vec![len]);
+    let (offset, strides) = check_layout(len, item_size, &shape, strides)?;
+    let c_strides = contiguous_strides(&shape, item_size, false);
+    let f_strides = contiguous_strides(&shape, item_size, true);
+    let is_c_contiguous = same_strides(&shape, &strides, &c_strides);
+    let is_f_contiguous = same_strides(&shape, &strides, &f_strides);
+    let contiguity_err = if flags & ffi::PyBUF_C_CONTIGUOUS == ffi::PyBUF_C_CONTIGUOUS {
+        !is_c_contiguous
+    } else if flags & ffi::PyBUF_F_CONTIGUOUS == ffi::PyBUF_F_CONTIGUOUS {
+        !is_f_contiguous
+    } else if flags & ffi::PyBUF_ANY_CONTIGUOUS == ffi::PyBUF_ANY_CONTIGUOUS {
+        !is_c_contiguous && !is_f_contiguous
+    } else {
+        flags & ffi::PyBUF_STRIDES != ffi::PyBUF_STRIDES && !is_c_contiguous
+    };
+    if contiguity_err {
+        return Err(PyBufferError::new_err(
+            "buffer does not have the requested contiguity",
+        ));
+    }
+
+    let layout = T::Item::layout()
+        .ok_or_else(|| PyBufferError::new_err("the element type has no buffer layout"))?;
+    let format = CString::new(layout.to_string())
+        .map_err(|_| PyBufferError::new_err("buffer format contains a nul byte"))?;
+    let count: usize = shape.iter().product();
+    let layout = Box::new(ExportedLayout {
+        format,
+        shape: shape.iter().map(|&dim| dim as ffi::Py_ssize_t).collect(),
+        strides,
+        writable,
+    });
+
+    (*view).obj = cell.as_ptr();
+    ffi::Py_INCREF((*view).obj);
+    (*view).buf = (buf as *mut u8).add(offset) as *mut c_void;
+    (*view).len = (count * item_size) as ffi::Py_ssize_t;
+    (*view).readonly = (!writable) as c_int;
+    (*view).itemsize = item_size as ffi::Py_ssize_t;
+    (*view).format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+        layout.format.as_ptr() as *mut _
+    } else {
+        ptr::null_mut()
+    };
+    (*view).ndim = layout.shape.len() as c_int;
+    (*view).shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+        layout.shape.as_ptr() as *mut _
+    } else {
+        ptr::null_mut()
+    };
+    (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+        layout.strides.as_ptr() as *mut _
+    } else {
+        ptr::null_mut()
+    };
+    (*view).suboffsets = ptr::null_mut();
+    (*view).internal = Box::into_raw(layout) as *mut c_void;
+
+    cell.set_borrow_flag(flag.add_export(writable));
+    Ok(())
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn export_getbuffer<T: BufferExport>(
+    slf: *mut ffi::PyObject,
+    view: *mut ffi::Py_buffer,
+    flags: c_int,
+) -> c_int {
+    crate::callback_body!(py, {
+        let cell = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let result = export_buffer(cell, view, flags);
+        if result.is_err() && !view.is_null() {
+            (*view).obj = ptr::null_mut();
+        }
+        result
+    })
+}
+
+#[doc(hidden)]
+pub unsafe extern "C" fn export_releasebuffer<T: BufferExport>(
+    slf: *mut ffi::PyObject,
+    view: *mut ffi::Py_buffer,
+) {
+    crate::callback_body!(py, {
+        let cell = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let layout = Box::from_raw((*view).internal as *mut ExportedLayout);
+        cell.set_borrow_flag(cell.get_borrow_flag().remove_export(layout.writable));
+        Ok::<_, PyErr>(())
+    })
+}
+
 #[doc(hidden)]
 pub unsafe extern "C" fn getbuffer<T>(
     slf: *mut ffi::PyObject,
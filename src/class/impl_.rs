@@ -4,6 +4,7 @@ use crate::{
     ffi,
     impl_::freelist::FreeList,
     pycell::PyCellLayout,
+    pyclass::PyMetaclass,
     pyclass_init::PyObjectInit,
     type_object::{PyLayout, PyTypeObject},
     PyClass, PyMethodDefType, PyNativeType, PyTypeInfo, Python,
@@ -57,6 +58,9 @@ pub trait PyClassImpl: Sized {
     /// Base class
     type BaseType: PyTypeInfo + PyTypeObject + PyClassBaseType;
 
+    /// #[pyclass(metaclass=...)], or the metaclass of the base class
+    type Metaclass: PyMetaclass;
+
     /// This handles following two situations:
     /// 1. In case `T` is `Send`, stub `ThreadChecker` is used and does nothing.
     ///    This implementation is used by default. Compile fails if `T: !Send`.
@@ -294,6 +298,22 @@ methods_trait!(PyContextProtocolMethods, context_protocol_methods);
 methods_trait!(PyDescrProtocolMethods, descr_protocol_methods);
 methods_trait!(PyMappingProtocolMethods, mapping_protocol_methods);
 methods_trait!(PyNumberProtocolMethods, number_protocol_methods);
+methods_trait!(PyTzInfoMethods, tzinfo_methods);
+
+#[cfg(not(Py_LIMITED_API))]
+impl<T: crate::types::TzInfo> PyTzInfoMethods<T> for PyClassImplCollector<T> {
+    fn tzinfo_methods(self) -> &'static [PyMethodDefType] {
+        crate::types::TzInfoMethods::<T>::METHOD_DEFS
+    }
+}
+
+methods_trait!(PyPickleMethods, pickle_methods);
+
+impl<T: crate::class::pickle::PickleState> PyPickleMethods<T> for PyClassImplCollector<T> {
+    fn pickle_methods(self) -> &'static [PyMethodDefType] {
+        crate::class::pickle::PickleMethods::<T>::METHOD_DEFS
+    }
+}
 
 // On Python < 3.9 setting the buffer protocol using slots doesn't work, so these procs are used
 // on those versions to set the slots manually (on the limited API).
@@ -314,6 +334,32 @@ impl<T> PyBufferProtocolProcs<T> for &'_ PyClassImplCollector<T> {
     }
 }
 
+#[cfg(not(Py_LIMITED_API))]
+impl<T: crate::class::buffer::BufferExport> PyBufferProtocolSlots<T> for PyClassImplCollector<T> {
+    fn buffer_protocol_slots(self) -> &'static [ffi::PyType_Slot] {
+        &[
+            ffi::PyType_Slot {
+                slot: ffi::Py_bf_getbuffer,
+                pfunc: crate::class::buffer::export_getbuffer::<T> as _,
+            },
+            ffi::PyType_Slot {
+                slot: ffi::Py_bf_releasebuffer,
+                pfunc: crate::class::buffer::export_releasebuffer::<T> as _,
+            },
+        ]
+    }
+}
+
+#[cfg(not(Py_LIMITED_API))]
+impl<T: crate::class::buffer::BufferExport> PyBufferProtocolProcs<T> for PyClassImplCollector<T> {
+    fn buffer_procs(self) -> Option<&'static PyBufferProcs> {
+        Some(&PyBufferProcs {
+            bf_getbuffer: Some(crate::class::buffer::export_getbuffer::<T>),
+            bf_releasebuffer: Some(crate::class::buffer::export_releasebuffer::<T>),
+        })
+    }
+}
+
 // Thread checkers
 
 #[doc(hidden)]
@@ -388,6 +434,7 @@ pub trait PyClassBaseType: Sized {
     type BaseNativeType;
     type ThreadChecker: PyClassThreadChecker<Self>;
     type Initializer: PyObjectInit<Self>;
+    type Metaclass: PyMetaclass;
 }
 
 /// All PyClasses can be used as a base type.
@@ -398,6 +445,7 @@ impl<T: PyClass> PyClassBaseType for T {
     type BaseNativeType = T::BaseNativeType;
     type ThreadChecker = T::ThreadChecker;
     type Initializer = crate::pyclass_init::PyClassInitializer<Self>;
+    type Metaclass = T::Metaclass;
 }
 
 /// Default new implementation
@@ -19,13 +19,15 @@ pub mod mapping;
 #[doc(hidden)]
 pub mod methods;
 pub mod number;
+#[doc(hidden)]
+pub mod pickle;
 pub mod pyasync;
 pub mod sequence;
 
 pub use self::basic::PyObjectProtocol;
 #[cfg(not(Py_LIMITED_API))]
 #[cfg_attr(docsrs, doc(cfg(not(Py_LIMITED_API))))]
-pub use self::buffer::PyBufferProtocol;
+pub use self::buffer::{BufferExport, PyBufferProtocol};
 pub use self::context::PyContextProtocol;
 pub use self::descr::PyDescrProtocol;
 pub use self::gc::{PyGCProtocol, PyTraverseError, PyVisit};
@@ -0,0 +1,163 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+//! Implementation of `#[pyclass(pickle)]`.
+//!
+//! Instances pickle as a call to the hidden class method `_pyo3_unpickle`, which is looked up
+//! through the class and so through the `module` given to `#[pyclass]`. Its single argument is
+//! the state returned by [`PickleState::to_state`]. Like any class method, it can also be called
+//! from Python code, and it creates the instance from the state without calling `#[new]`.
+//!
+//! Instances which have a non-empty `__dict__`, such as instances of Python subclasses, also
+//! pickle the `__dict__` as the third item returned by `__reduce__`. Unpickling passes it to the
+//! generated `__setstate__`, which updates the `__dict__` when given a `dict` for an instance
+//! which has one, and otherwise replaces the Rust value with the one converted from the state.
+
+use crate::class::methods::{PyCFunction, PyMethodDef, PyMethodDefType, PyMethodType};
+use crate::exceptions::{PyAttributeError, PyTypeError, PyValueError};
+use crate::once_cell::GILOnceCell;
+use crate::types::{PyDict, PyTuple, PyType};
+use crate::{
+    ffi, IntoPy, PyAny, PyCell, PyClass, PyClassInitializer, PyObject, PyResult, PyTypeInfo, Python,
+};
+
+/// Conversion of a `#[pyclass(pickle)]` value to and from the state stored in a pickle.
+///
+/// This is implemented by the `#[pyclass]` macro.
+pub trait PickleState: PyClass<BaseType = PyAny> + Sized {
+    fn to_state(&self, py: Python) -> PyResult<PyObject>;
+    fn from_state(state: &PyAny) -> PyResult<Self>;
+}
+
+/// The method definitions added to a `#[pyclass(pickle)]`.
+pub struct PickleMethods<T>(std::marker::PhantomData<T>);
+
+impl<T: PickleState> PickleMethods<T> {
+    pub const METHOD_DEFS: &'static [PyMethodDefType] = &[
+        PyMethodDefType::Method(PyMethodDef {
+            ml_name: "__reduce__\0",
+            ml_meth: PyMethodType::PyCFunction(PyCFunction(reduce::<T>)),
+            ml_flags: ffi::METH_NOARGS,
+            ml_doc: "\0",
+        }),
+        PyMethodDefType::Method(PyMethodDef {
+            ml_name: "__setstate__\0",
+            ml_meth: PyMethodType::PyCFunction(PyCFunction(setstate::<T>)),
+            ml_flags: ffi::METH_O,
+            ml_doc: "\0",
+        }),
+        PyMethodDefType::Class(PyMethodDef {
+            ml_name: "_pyo3_unpickle\0",
+            ml_meth: PyMethodType::PyCFunction(PyCFunction(unpickle::<T>)),
+            ml_flags: ffi::METH_O | ffi::METH_CLASS,
+            ml_doc: "\0",
+        }),
+    ];
+}
+
+/// Checks the number of fields in the state of a `#[pyclass(pickle)]` which pickles its fields
+/// as a tuple.
+pub fn fields_state(state: &PyAny, len: usize) -> PyResult<&PyTuple> {
+    let state: &PyTuple = state.downcast()?;
+    if state.len() != len {
+        return Err(PyValueError::new_err(format!(
+            "expected pickled state with {} fields, got {}",
+            len,
+            state.len()
+        )));
+    }
+    Ok(state)
+}
+
+unsafe extern "C" fn reduce<T: PickleState>(
+    slf: *mut ffi::PyObject,
+    _args: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback_body!(py, {
+        let cell = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let state = cell.try_borrow()?.to_state(py)?;
+        let unpickle = cell.get_type().getattr("_pyo3_unpickle")?;
+        let reduced = match instance_dict(py, cell)? {
+            Some(dict) if !dict.is_empty() => {
+                IntoPy::<PyObject>::into_py((unpickle, (state,), dict), py)
+            }
+            _ => IntoPy::<PyObject>::into_py((unpickle, (state,)), py),
+        };
+        PyResult::Ok(reduced)
+    })
+}
+
+unsafe extern "C" fn setstate<T: PickleState>(
+    slf: *mut ffi::PyObject,
+    state: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback_body!(py, {
+        let cell = py.from_borrowed_ptr::<PyCell<T>>(slf);
+        let state = py.from_borrowed_ptr::<PyAny>(state);
+        if let (Ok(state), Some(dict)) = (state.downcast::<PyDict>(), instance_dict(py, cell)?) {
+            dict.call_method1("update", (state,))?;
+        } else {
+            let value = T::from_state(state)?;
+            *cell.try_borrow_mut()? = value;
+        }
+        PyResult::Ok(())
+    })
+}
+
+unsafe extern "C" fn unpickle<T: PickleState>(
+    cls: *mut ffi::PyObject,
+    state: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback_body!(py, {
+        let cls = py.from_borrowed_ptr::<PyType>(cls);
+        check_unpickle_type::<T>(py, cls)?;
+        let value = T::from_state(py.from_borrowed_ptr(state))?;
+        // `cls` may be a Python subclass of `T`
+        let cell =
+            PyClassInitializer::from(value).create_cell_from_subtype(py, cls.as_type_ptr())?;
+        PyResult::Ok(PyObject::from_owned_ptr(py, cell as _))
+    })
+}
+
+/// Checks that `cls` is `T` or a Python subclass of it. A `#[pyclass(extends = T)]` inherits
+/// `_pyo3_unpickle` too, but its own Rust data would be left uninitialized.
+unsafe fn check_unpickle_type<T: PickleState>(py: Python, cls: &PyType) -> PyResult<()> {
+    let target = T::type_object_raw(py);
+    let python_dealloc = python_class_dealloc(py)?;
+    let mut ty = cls;
+    // Python classes all have the same `tp_dealloc`, unlike the classes of Rust types
+    while ty.as_type_ptr() != target {
+        if ffi::PyType_GetSlot(ty.as_type_ptr(), ffi::Py_tp_dealloc) as usize != python_dealloc {
+            return Err(PyTypeError::new_err(format!(
+                "cannot unpickle {} from the state of {}, as {} has Rust data of its own",
+                cls.name()?,
+                T::NAME,
+                ty.name()?
+            )));
+        }
+        ty = ty.getattr("__base__")?.downcast()?;
+    }
+    Ok(())
+}
+
+/// Returns the `tp_dealloc` which classes created by Python code share.
+fn python_class_dealloc(py: Python) -> PyResult<usize> {
+    static DEALLOC: GILOnceCell<usize> = GILOnceCell::new();
+    if let Some(&dealloc) = DEALLOC.get(py) {
+        return Ok(dealloc);
+    }
+    let class: &PyType = py
+        .get_type::<PyType>()
+        .call1(("_", PyTuple::empty(py), PyDict::new(py)))?
+        .downcast()?;
+    let dealloc = unsafe { ffi::PyType_GetSlot(class.as_type_ptr(), ffi::Py_tp_dealloc) };
+    Ok(*DEALLOC.get_or_init(py, || dealloc as usize))
+}
+
+/// Returns the `__dict__` of an instance, if it has one.
+fn instance_dict<'py>(py: Python<'py>, obj: &'py PyAny) -> PyResult<Option<&'py PyDict>> {
+    match obj.getattr("__dict__") {
+        Ok(dict) => Ok(Some(dict.downcast()?)),
+        Err(err) if err.is_instance::<PyAttributeError>(py) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,104 @@
+#![cfg(all(feature = "arrayvec", min_const_generics))]
+//! Conversions to and from [arrayvec](https://docs.rs/arrayvec/)’s `ArrayVec`.
+//!
+//! `ArrayVec` converts to a Python `list`, and can be extracted from any sequence like `Vec`.
+//! Extracting a sequence with more elements than the capacity of the `ArrayVec` raises
+//! `ValueError`.
+//!
+//! `arrayvec` 0.7 uses const generics, so this feature requires Rust 1.51 or newer.
+//!
+//! # Setup
+//!
+//! To use this feature, add this to your **`Cargo.toml`**:
+//!
+//! ```toml
+//! [dependencies]
+//! # change * to the latest versions
+//! arrayvec = "*"
+// workaround for `extended_key_value_attributes`: https://github.com/rust-lang/rust/issues/82768#issuecomment-803935643
+#![cfg_attr(docsrs, cfg_attr(docsrs, doc = concat!("pyo3 = { version = \"", env!("CARGO_PKG_VERSION"),  "\", features = [\"arrayvec\"] }")))]
+#![cfg_attr(
+    not(docsrs),
+    doc = "pyo3 = { version = \"*\", features = [\"arrayvec\"] }"
+)]
+//! ```
+//!
+//! Note that you must use compatible versions of arrayvec and PyO3.
+//! The required arrayvec version may vary based on the version of PyO3.
+//!
+//! # Example
+//!
+//! ```rust
+//! use arrayvec::ArrayVec;
+//! use pyo3::prelude::*;
+//!
+//! #[pyfunction]
+//! fn rgb(color: ArrayVec<u8, 3>) -> String {
+//!     color.iter().map(|c| format!("{:02x}", c)).collect()
+//! }
+//! ```
+
+use crate::exceptions::PyValueError;
+use crate::types::{extract_sequence_into, PyList};
+use crate::{FromPyObject, IntoPy, PyAny, PyObject, PyResult, Python, ToPyObject};
+use arrayvec::ArrayVec;
+
+impl<T: ToPyObject, const CAP: usize> ToPyObject for ArrayVec<T, CAP> {
+    fn to_object(&self, py: Python) -> PyObject {
+        self.as_slice().to_object(py)
+    }
+}
+
+impl<T: IntoPy<PyObject>, const CAP: usize> IntoPy<PyObject> for ArrayVec<T, CAP> {
+    fn into_py(self, py: Python) -> PyObject {
+        PyList::new(py, self.into_iter().map(|e| e.into_py(py))).into()
+    }
+}
+
+impl<'a, T: FromPyObject<'a>, const CAP: usize> FromPyObject<'a> for ArrayVec<T, CAP> {
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        extract_sequence_into(
+            obj,
+            |_| ArrayVec::new(),
+            |v, item| {
+                v.try_push(item).map_err(|_| {
+                    PyValueError::new_err(format!(
+                        "expected a sequence of at most {} elements",
+                        CAP
+                    ))
+                })
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arrayvec_roundtrip() {
+        Python::with_gil(|py| {
+            let v: ArrayVec<i32, 3> = [1, 2, 3].into();
+            let obj = v.to_object(py);
+            crate::py_run!(py, obj, "assert obj == [1, 2, 3]");
+            assert_eq!(
+                obj.extract::<ArrayVec<i32, 4>>(py).unwrap().as_slice(),
+                &[1, 2, 3]
+            );
+            assert_eq!(
+                v.into_py(py).extract::<ArrayVec<i32, 3>>(py).unwrap().len(),
+                3
+            );
+        });
+    }
+
+    #[test]
+    fn test_arrayvec_capacity() {
+        Python::with_gil(|py| {
+            let obj = vec![1, 2, 3].to_object(py);
+            let err = obj.extract::<ArrayVec<i32, 2>>(py).unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+        });
+    }
+}
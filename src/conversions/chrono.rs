@@ -0,0 +1,590 @@
+#![cfg(all(feature = "chrono", not(Py_LIMITED_API)))]
+//! Conversions to and from [chrono](https://docs.rs/chrono/)’s `Duration`,
+//! `NaiveDate`, `NaiveTime`, `NaiveDateTime`, `DateTime<Utc>`, `DateTime<FixedOffset>`,
+//! `FixedOffset` and `Utc`.
+//!
+//! | Rust                    | Python                                              |
+//! | ----------------------- | --------------------------------------------------- |
+//! | `Duration`              | `datetime.timedelta`                                |
+//! | `NaiveDate`             | `datetime.date`                                     |
+//! | `NaiveTime`             | `datetime.time`                                     |
+//! | `NaiveDateTime`         | `datetime.datetime` without `tzinfo`                |
+//! | `DateTime<Utc>`         | `datetime.datetime` with `tzinfo=datetime.timezone.utc` |
+//! | `DateTime<FixedOffset>` | `datetime.datetime` with a `datetime.timezone` `tzinfo` |
+//! | `FixedOffset`, `Utc`    | `datetime.timezone`                                 |
+//!
+//! Python's `datetime` types have a resolution of one microsecond, so nanoseconds are truncated
+//! when converting from Rust. Python has no leap seconds, so a chrono leap second is truncated to
+//! the last microsecond of the second before it, e.g. `23:59:60.5` becomes `23:59:59.999999`.
+//! The `fold` of Python times only disambiguates repeated wall times, which is already reflected
+//! in the `utcoffset()` of aware `datetime`s, so it is ignored when extracting.
+//!
+//! The conversions use the `datetime` C-API, so they are not available with the `abi3` feature.
+//!
+//! Extracting a `DateTime<Utc>` accepts any aware `datetime` and converts it to UTC, while
+//! `DateTime<FixedOffset>` keeps the original offset. Naive and aware `datetime`s are never
+//! implicitly converted into each other.
+//!
+//! # Setup
+//!
+//! To use this feature, add this to your **`Cargo.toml`**:
+//!
+//! ```toml
+//! [dependencies]
+//! # change * to the latest versions
+//! chrono = "*"
+// workaround for `extended_key_value_attributes`: https://github.com/rust-lang/rust/issues/82768#issuecomment-803935643
+#![cfg_attr(docsrs, cfg_attr(docsrs, doc = concat!("pyo3 = { version = \"", env!("CARGO_PKG_VERSION"),  "\", features = [\"chrono\"] }")))]
+#![cfg_attr(
+    not(docsrs),
+    doc = "pyo3 = { version = \"*\", features = [\"chrono\"] }"
+)]
+//! ```
+//!
+//! Note that you must use compatible versions of chrono and PyO3.
+//! The required chrono version may vary based on the version of PyO3.
+//!
+//! # Example: Convert a `datetime.datetime` to chrono's `DateTime<Utc>`
+//!
+//! ```rust
+//! use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+//! use pyo3::{Python, ToPyObject};
+//!
+//! fn main() {
+//!     pyo3::prepare_freethreaded_python();
+//!     Python::with_gil(|py| {
+//!         let naive = NaiveDate::from_ymd_opt(2021, 8, 9)
+//!             .unwrap()
+//!             .and_hms_micro_opt(12, 30, 0, 500)
+//!             .unwrap();
+//!         let time = Utc.from_utc_datetime(&naive);
+//!         let py_time = time.to_object(py);
+//!         let one_day: Duration = py
+//!             .eval("__import__('datetime').timedelta(days=1)", None, None)
+//!             .unwrap()
+//!             .extract()
+//!             .unwrap();
+//!         let tomorrow: DateTime<Utc> = py_time
+//!             .call_method1(py, "__add__", (one_day,))
+//!             .unwrap()
+//!             .extract(py)
+//!             .unwrap();
+//!         assert_eq!(tomorrow, time + one_day);
+//!     });
+//! }
+//! ```
+use crate::exceptions::{PyTypeError, PyValueError};
+use crate::types::{
+    PyDate, PyDateAccess, PyDateTime, PyDelta, PyDeltaAccess, PyTime, PyTimeAccess, PyTzInfo,
+};
+use crate::{FromPyObject, IntoPy, PyAny, PyNativeType, PyObject, PyResult, Python, ToPyObject};
+use chrono::offset::{FixedOffset, Offset, TimeZone, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::convert::TryFrom;
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Converts a `Duration` to a `datetime.timedelta`.
+///
+/// # Panics
+///
+/// Panics if the `Duration` is longer than `timedelta.max`, 999999999 days, in either direction.
+impl ToPyObject for Duration {
+    fn to_object(&self, py: Python) -> PyObject {
+        // `timedelta` normalizes the components, so they may all be negative
+        let days = self.num_days();
+        let rest = *self - Duration::days(days);
+        let seconds = rest.num_seconds();
+        let microseconds = (rest - Duration::seconds(seconds))
+            .num_microseconds()
+            .expect("less than one second of microseconds always fits in an i64");
+        // `timedelta` itself rejects more than 999999999 days
+        let days = i32::try_from(days).expect("Duration is out of range for timedelta");
+        PyDelta::new(py, days, seconds as i32, microseconds as i32, true)
+            .expect("Duration is out of range for timedelta")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for Duration {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for Duration {
+    fn extract(ob: &PyAny) -> PyResult<Duration> {
+        let delta: &PyDelta = ob.downcast()?;
+        // timedelta is limited to +-999999999 days, which chrono can always represent
+        Ok(Duration::days(i64::from(delta.get_days()))
+            + Duration::seconds(i64::from(delta.get_seconds()))
+            + Duration::microseconds(i64::from(delta.get_microseconds())))
+    }
+}
+
+impl ToPyObject for NaiveDate {
+    fn to_object(&self, py: Python) -> PyObject {
+        PyDate::new(py, self.year(), self.month() as u8, self.day() as u8)
+            .expect("failed to construct date")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for NaiveDate {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for NaiveDate {
+    fn extract(ob: &PyAny) -> PyResult<NaiveDate> {
+        let date: &PyDate = ob.downcast()?;
+        naive_date(date)
+    }
+}
+
+impl ToPyObject for NaiveTime {
+    fn to_object(&self, py: Python) -> PyObject {
+        PyTime::new(
+            py,
+            self.hour() as u8,
+            self.minute() as u8,
+            self.second() as u8,
+            microsecond(self),
+            None,
+        )
+        .expect("failed to construct time")
+        .into()
+    }
+}
+
+impl IntoPy<PyObject> for NaiveTime {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for NaiveTime {
+    fn extract(ob: &PyAny) -> PyResult<NaiveTime> {
+        let time: &PyTime = ob.downcast()?;
+        naive_time(time)
+    }
+}
+
+impl ToPyObject for NaiveDateTime {
+    fn to_object(&self, py: Python) -> PyObject {
+        naive_datetime_to_py(py, self, None)
+    }
+}
+
+impl IntoPy<PyObject> for NaiveDateTime {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for NaiveDateTime {
+    fn extract(ob: &PyAny) -> PyResult<NaiveDateTime> {
+        let datetime: &PyDateTime = ob.downcast()?;
+        if !datetime.getattr("tzinfo")?.is_none() {
+            return Err(PyTypeError::new_err(
+                "expected a naive datetime, got a datetime with tzinfo",
+            ));
+        }
+        naive_datetime(datetime)
+    }
+}
+
+impl<Tz: TimeZone> ToPyObject for DateTime<Tz> {
+    fn to_object(&self, py: Python) -> PyObject {
+        let tzinfo = self.offset().fix().to_object(py);
+        naive_datetime_to_py(py, &self.naive_local(), Some(&tzinfo))
+    }
+}
+
+impl<Tz: TimeZone> IntoPy<PyObject> for DateTime<Tz> {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for DateTime<FixedOffset> {
+    fn extract(ob: &PyAny) -> PyResult<DateTime<FixedOffset>> {
+        let datetime: &PyDateTime = ob.downcast()?;
+        let offset = datetime.call_method0("utcoffset")?;
+        if offset.is_none() {
+            return Err(PyTypeError::new_err(
+                "expected an aware datetime, got a datetime without tzinfo",
+            ));
+        }
+        let offset = fixed_offset(offset)?;
+        offset
+            .from_local_datetime(&naive_datetime(datetime)?)
+            .single()
+            .ok_or_else(|| PyValueError::new_err("datetime is out of range for chrono"))
+    }
+}
+
+impl FromPyObject<'_> for DateTime<Utc> {
+    fn extract(ob: &PyAny) -> PyResult<DateTime<Utc>> {
+        let datetime: DateTime<FixedOffset> = ob.extract()?;
+        Ok(datetime.with_timezone(&Utc))
+    }
+}
+
+impl ToPyObject for FixedOffset {
+    fn to_object(&self, py: Python) -> PyObject {
+        PyDelta::new(py, 0, self.local_minus_utc(), 0, true)
+            .and_then(|offset| PyTzInfo::timezone(py, offset))
+            .expect("failed to construct timezone")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for FixedOffset {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for FixedOffset {
+    /// Extracts the offset of a `tzinfo` which does not depend on the date, such as
+    /// `datetime.timezone`.
+    fn extract(ob: &PyAny) -> PyResult<FixedOffset> {
+        let tzinfo: &PyTzInfo = ob.downcast()?;
+        let offset = tzinfo.call_method1("utcoffset", (ob.py().None(),))?;
+        if offset.is_none() {
+            return Err(PyValueError::new_err(format!(
+                "{} is not a fixed offset timezone",
+                tzinfo.repr()?
+            )));
+        }
+        fixed_offset(offset)
+    }
+}
+
+impl ToPyObject for Utc {
+    fn to_object(&self, py: Python) -> PyObject {
+        PyTzInfo::utc(py).expect("failed to get UTC").into()
+    }
+}
+
+impl IntoPy<PyObject> for Utc {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for Utc {
+    fn extract(ob: &PyAny) -> PyResult<Utc> {
+        let offset: FixedOffset = ob.extract()?;
+        if offset.local_minus_utc() == 0 {
+            Ok(Utc)
+        } else {
+            Err(PyValueError::new_err(format!(
+                "expected a UTC timezone, got {}",
+                ob.repr()?
+            )))
+        }
+    }
+}
+
+/// Returns the microseconds of a chrono time, truncating a leap second to the last microsecond
+/// of the second before it.
+fn microsecond(time: &impl Timelike) -> u32 {
+    (time.nanosecond() / 1000).min(999_999)
+}
+
+fn naive_date(date: &impl PyDateAccess) -> PyResult<NaiveDate> {
+    NaiveDate::from_ymd_opt(
+        date.get_year(),
+        u32::from(date.get_month()),
+        u32::from(date.get_day()),
+    )
+    .ok_or_else(|| PyValueError::new_err("date is out of range for chrono"))
+}
+
+fn naive_time(time: &impl PyTimeAccess) -> PyResult<NaiveTime> {
+    NaiveTime::from_hms_micro_opt(
+        u32::from(time.get_hour()),
+        u32::from(time.get_minute()),
+        u32::from(time.get_second()),
+        time.get_microsecond(),
+    )
+    .ok_or_else(|| PyValueError::new_err("time is out of range for chrono"))
+}
+
+fn naive_datetime(datetime: &PyDateTime) -> PyResult<NaiveDateTime> {
+    Ok(NaiveDateTime::new(
+        naive_date(datetime)?,
+        naive_time(datetime)?,
+    ))
+}
+
+fn naive_datetime_to_py(
+    py: Python,
+    datetime: &NaiveDateTime,
+    tzinfo: Option<&PyObject>,
+) -> PyObject {
+    PyDateTime::new(
+        py,
+        datetime.year(),
+        datetime.month() as u8,
+        datetime.day() as u8,
+        datetime.hour() as u8,
+        datetime.minute() as u8,
+        datetime.second() as u8,
+        microsecond(datetime),
+        tzinfo,
+    )
+    .expect("failed to construct datetime")
+    .into()
+}
+
+/// Converts the `timedelta` returned by `tzinfo.utcoffset()` to a `FixedOffset`.
+fn fixed_offset(offset: &PyAny) -> PyResult<FixedOffset> {
+    let delta: &PyDelta = offset.downcast()?;
+    if delta.get_microseconds() != 0 {
+        return Err(PyValueError::new_err(
+            "UTC offsets with microseconds are not supported by chrono",
+        ));
+    }
+    let seconds = i64::from(delta.get_days()) * SECONDS_PER_DAY + i64::from(delta.get_seconds());
+    // `utcoffset()` is always strictly between -24 and 24 hours
+    FixedOffset::east_opt(seconds as i32)
+        .ok_or_else(|| PyValueError::new_err("UTC offset is out of range for chrono"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::py_run;
+
+    #[test]
+    fn test_duration_roundtrip() {
+        Python::with_gil(|py| {
+            for duration in &[
+                Duration::days(2) + Duration::microseconds(5),
+                Duration::seconds(-1),
+                -Duration::days(3) - Duration::microseconds(1),
+            ] {
+                let obj = duration.to_object(py);
+                assert!(obj.as_ref(py).is_instance::<PyDelta>().unwrap());
+                assert_eq!(obj.extract::<Duration>(py).unwrap(), *duration);
+            }
+            let obj = (-Duration::seconds(1)).to_object(py);
+            py_run!(
+                py,
+                obj,
+                "import datetime; assert obj == datetime.timedelta(seconds=-1)"
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Duration is out of range for timedelta")]
+    fn test_duration_out_of_range_panics() {
+        Python::with_gil(|py| {
+            Duration::days(1_000_000_000).to_object(py);
+        });
+    }
+
+    #[test]
+    fn test_naive_date_and_time() {
+        Python::with_gil(|py| {
+            let date = NaiveDate::from_ymd_opt(2021, 8, 9).unwrap();
+            let obj = date.to_object(py);
+            py_run!(
+                py,
+                obj,
+                "import datetime; assert obj == datetime.date(2021, 8, 9)"
+            );
+            assert_eq!(obj.extract::<NaiveDate>(py).unwrap(), date);
+
+            let time = NaiveTime::from_hms_micro_opt(12, 30, 1, 999_999).unwrap();
+            let obj = time.to_object(py);
+            py_run!(
+                py,
+                obj,
+                "import datetime; assert obj == datetime.time(12, 30, 1, 999999)"
+            );
+            assert_eq!(obj.extract::<NaiveTime>(py).unwrap(), time);
+
+            // Nanoseconds are truncated
+            let time = NaiveTime::from_hms_nano_opt(0, 0, 0, 1_999).unwrap();
+            let roundtrip: NaiveTime = time.into_py(py).extract(py).unwrap();
+            assert_eq!(
+                roundtrip,
+                NaiveTime::from_hms_micro_opt(0, 0, 0, 1).unwrap()
+            );
+        });
+    }
+
+    #[test]
+    fn test_leap_second_truncated() {
+        Python::with_gil(|py| {
+            let leap = NaiveDate::from_ymd_opt(2016, 12, 31)
+                .unwrap()
+                .and_hms_micro_opt(23, 59, 59, 1_000_500)
+                .unwrap();
+            let obj = leap.to_object(py);
+            py_run!(
+                py,
+                obj,
+                "assert (obj.second, obj.microsecond, obj.fold) == (59, 999999, 0)"
+            );
+            assert_eq!(
+                obj.extract::<NaiveDateTime>(py).unwrap(),
+                NaiveDate::from_ymd_opt(2016, 12, 31)
+                    .unwrap()
+                    .and_hms_micro_opt(23, 59, 59, 999_999)
+                    .unwrap()
+            );
+
+            let leap = NaiveTime::from_hms_micro_opt(1, 30, 59, 1_000_500).unwrap();
+            let obj = leap.to_object(py);
+            py_run!(
+                py,
+                obj,
+                "assert (obj.second, obj.microsecond) == (59, 999999)"
+            );
+        });
+    }
+
+    #[cfg(not(PyPy))]
+    #[test]
+    fn test_fold_is_ignored() {
+        Python::with_gil(|py| {
+            for &(hour, minute) in &[(1, 30), (23, 59)] {
+                let time: NaiveTime = py
+                    .eval(
+                        &format!(
+                            "__import__('datetime').time({}, {}, 59, 500, fold=1)",
+                            hour, minute
+                        ),
+                        None,
+                        None,
+                    )
+                    .unwrap()
+                    .extract()
+                    .unwrap();
+                assert_eq!(
+                    time,
+                    NaiveTime::from_hms_micro_opt(hour, minute, 59, 500).unwrap()
+                );
+            }
+
+            // a repeated wall time at the end of DST in a zone where it ends at midnight
+            let globals = crate::types::PyDict::new(py);
+            py.run(
+                r#"
+import datetime
+class MidnightDst(datetime.tzinfo):
+    def utcoffset(self, dt):
+        return datetime.timedelta(hours=0 if dt.fold else 1)
+    def dst(self, dt):
+        return datetime.timedelta(hours=0 if dt.fold else 1)
+folded = datetime.datetime(2021, 10, 31, 23, 59, 59, tzinfo=MidnightDst(), fold=1)
+"#,
+                Some(globals),
+                None,
+            )
+            .unwrap();
+            let folded: DateTime<FixedOffset> =
+                globals.get_item("folded").unwrap().extract().unwrap();
+            let expected = NaiveDate::from_ymd_opt(2021, 10, 31)
+                .unwrap()
+                .and_hms_opt(23, 59, 59)
+                .unwrap();
+            assert_eq!(folded.naive_local(), expected);
+            assert_eq!(folded.offset().local_minus_utc(), 0);
+        });
+    }
+
+    #[test]
+    fn test_naive_datetime() {
+        Python::with_gil(|py| {
+            let datetime = NaiveDate::from_ymd_opt(2021, 8, 9)
+                .unwrap()
+                .and_hms_micro_opt(1, 2, 3, 4)
+                .unwrap();
+            let obj = datetime.to_object(py);
+            py_run!(
+                py,
+                obj,
+                "import datetime; assert obj == datetime.datetime(2021, 8, 9, 1, 2, 3, 4)"
+            );
+            assert_eq!(obj.extract::<NaiveDateTime>(py).unwrap(), datetime);
+
+            let aware = Utc.from_utc_datetime(&datetime).to_object(py);
+            let err = aware.extract::<NaiveDateTime>(py).unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+            let err = obj.extract::<DateTime<Utc>>(py).unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_aware_datetime() {
+        Python::with_gil(|py| {
+            let offset = FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap();
+            let datetime = offset
+                .from_local_datetime(
+                    &NaiveDate::from_ymd_opt(2021, 8, 9)
+                        .unwrap()
+                        .and_hms_micro_opt(12, 0, 0, 7)
+                        .unwrap(),
+                )
+                .unwrap();
+            let obj = datetime.to_object(py);
+            py_run!(
+                py,
+                obj,
+                r#"
+import datetime
+tz = datetime.timezone(datetime.timedelta(hours=5, minutes=30))
+assert obj == datetime.datetime(2021, 8, 9, 12, 0, 0, 7, tzinfo=tz)
+assert obj.tzinfo == tz
+"#
+            );
+            let roundtrip: DateTime<FixedOffset> = obj.extract(py).unwrap();
+            assert_eq!(roundtrip, datetime);
+            assert_eq!(roundtrip.offset(), &offset);
+
+            let utc: DateTime<Utc> = obj.extract(py).unwrap();
+            assert_eq!(utc, datetime.with_timezone(&Utc));
+            let obj = utc.to_object(py);
+            py_run!(
+                py,
+                obj,
+                "import datetime; assert obj.tzinfo is datetime.timezone.utc"
+            );
+        });
+    }
+
+    #[test]
+    fn test_timezones() {
+        Python::with_gil(|py| {
+            let offset = FixedOffset::west_opt(3600).unwrap();
+            let obj = offset.to_object(py);
+            assert!(obj.as_ref(py).is_instance::<PyTzInfo>().unwrap());
+            py_run!(
+                py,
+                obj,
+                "import datetime; assert obj == datetime.timezone(datetime.timedelta(hours=-1))"
+            );
+            assert_eq!(obj.extract::<FixedOffset>(py).unwrap(), offset);
+            assert!(obj.extract::<Utc>(py).is_err());
+
+            let obj = Utc.to_object(py);
+            assert_eq!(obj.extract::<Utc>(py).unwrap(), Utc);
+        });
+    }
+}
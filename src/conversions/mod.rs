@@ -1,8 +1,26 @@
 //! This module contains conversions between various Rust object and their representation in Python.
 
 mod array;
+#[cfg(all(feature = "arrayvec", min_const_generics))]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+pub mod arrayvec;
+#[cfg(all(feature = "chrono", not(Py_LIMITED_API)))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "chrono", not(Py_LIMITED_API)))))]
+pub mod chrono;
 #[cfg(feature = "indexmap")]
 #[cfg_attr(docsrs, doc(cfg(feature = "indexmap")))]
 pub mod indexmap;
+mod net;
 mod osstr;
 mod path;
+#[cfg(feature = "rust_decimal")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rust_decimal")))]
+pub mod rust_decimal;
+#[cfg(feature = "smallvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+pub mod smallvec;
+mod smart_pointers;
+mod time;
+#[cfg(feature = "uuid")]
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+pub mod uuid;
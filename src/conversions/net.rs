@@ -0,0 +1,359 @@
+//! Conversions between the `std::net` address types and Python's `ipaddress` module.
+//!
+//! IP addresses are created from their integer value and read back through their `packed`
+//! bytes, which avoids formatting and parsing strings. Socket addresses convert to the
+//! `(host, port)` and `(host, port, flowinfo, scope_id)` tuples used by the `socket` module.
+use crate::exceptions::PyValueError;
+use crate::once_cell::GILOnceCell;
+use crate::types::{PyBytes, PyString, PyTuple, PyType};
+use crate::{
+    FromPyObject, IntoPy, Py, PyAny, PyDowncastError, PyErr, PyNativeType, PyObject, PyResult,
+    Python, ToPyObject,
+};
+use std::convert::TryFrom;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::str::FromStr;
+
+fn ipv4_address_type(py: Python) -> PyResult<&PyType> {
+    static IPV4_ADDRESS: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+    import_type(py, &IPV4_ADDRESS, "IPv4Address")
+}
+
+fn ipv6_address_type(py: Python) -> PyResult<&PyType> {
+    static IPV6_ADDRESS: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+    import_type(py, &IPV6_ADDRESS, "IPv6Address")
+}
+
+fn import_type<'py>(
+    py: Python<'py>,
+    cell: &'py GILOnceCell<Py<PyType>>,
+    name: &str,
+) -> PyResult<&'py PyType> {
+    if let Some(ty) = cell.get(py) {
+        return Ok(ty.as_ref(py));
+    }
+    let ty: Py<PyType> = py
+        .import("ipaddress")?
+        .getattr(name)?
+        .downcast::<PyType>()
+        .map_err(PyErr::from)?
+        .into();
+    Ok(cell.get_or_init(py, || ty).as_ref(py))
+}
+
+/// Returns the `packed` bytes of `ob` if it is an instance of `ty`.
+fn packed<'p, T>(ob: &'p PyAny, ty: &PyType) -> PyResult<Option<T>>
+where
+    T: TryFrom<&'p [u8]>,
+{
+    if !ty.is_instance(ob)? {
+        return Ok(None);
+    }
+    // subclasses may override `packed`
+    let packed: &PyBytes = ob.getattr("packed")?.downcast()?;
+    T::try_from(packed.as_bytes()).map(Some).map_err(|_| {
+        PyValueError::new_err(format!(
+            "{}.packed has {} bytes, expected {}",
+            ty.name().unwrap_or("address"),
+            packed.as_bytes().len(),
+            std::mem::size_of::<T>()
+        ))
+    })
+}
+
+/// Parses `ob` with `FromStr` if it is a Python `str`.
+fn parse<T: FromStr>(ob: &PyAny, expected: &str) -> PyResult<Option<T>> {
+    let string = match ob.downcast::<PyString>() {
+        Ok(string) => string.to_str()?,
+        Err(_) => return Ok(None),
+    };
+    string.parse().map(Some).map_err(|_| {
+        PyValueError::new_err(format!("{:?} does not appear to be {}", string, expected))
+    })
+}
+
+impl ToPyObject for Ipv4Addr {
+    fn to_object(&self, py: Python) -> PyObject {
+        ipv4_address_type(py)
+            .and_then(|ty| ty.call1((u32::from(*self),)))
+            .expect("failed to create ipaddress.IPv4Address")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for Ipv4Addr {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for Ipv4Addr {
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        if let Some(octets) = packed::<[u8; 4]>(ob, ipv4_address_type(ob.py())?)? {
+            return Ok(Ipv4Addr::from(octets));
+        }
+        parse(ob, "an IPv4 address")?.ok_or_else(|| PyDowncastError::new(ob, "IPv4Address").into())
+    }
+}
+
+impl ToPyObject for Ipv6Addr {
+    fn to_object(&self, py: Python) -> PyObject {
+        ipv6_address_type(py)
+            .and_then(|ty| ty.call1((u128::from(*self),)))
+            .expect("failed to create ipaddress.IPv6Address")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for Ipv6Addr {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for Ipv6Addr {
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        if let Some(octets) = packed::<[u8; 16]>(ob, ipv6_address_type(ob.py())?)? {
+            return Ok(Ipv6Addr::from(octets));
+        }
+        parse(ob, "an IPv6 address")?.ok_or_else(|| PyDowncastError::new(ob, "IPv6Address").into())
+    }
+}
+
+impl ToPyObject for IpAddr {
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            IpAddr::V4(addr) => addr.to_object(py),
+            IpAddr::V6(addr) => addr.to_object(py),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for IpAddr {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for IpAddr {
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        let py = ob.py();
+        if let Some(octets) = packed::<[u8; 4]>(ob, ipv4_address_type(py)?)? {
+            return Ok(IpAddr::from(octets));
+        }
+        if let Some(octets) = packed::<[u8; 16]>(ob, ipv6_address_type(py)?)? {
+            return Ok(IpAddr::from(octets));
+        }
+        parse(ob, "an IPv4 or IPv6 address")?
+            .ok_or_else(|| PyDowncastError::new(ob, "IPv4Address | IPv6Address").into())
+    }
+}
+
+impl ToPyObject for SocketAddrV4 {
+    fn to_object(&self, py: Python) -> PyObject {
+        (self.ip().to_string(), self.port()).to_object(py)
+    }
+}
+
+impl IntoPy<PyObject> for SocketAddrV4 {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for SocketAddrV4 {
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        if let Some(addr) = parse(ob, "an IPv4 socket address")? {
+            return Ok(addr);
+        }
+        let (ip, port) = ob.extract()?;
+        Ok(SocketAddrV4::new(ip, port))
+    }
+}
+
+impl ToPyObject for SocketAddrV6 {
+    fn to_object(&self, py: Python) -> PyObject {
+        (
+            self.ip().to_string(),
+            self.port(),
+            self.flowinfo(),
+            self.scope_id(),
+        )
+            .to_object(py)
+    }
+}
+
+impl IntoPy<PyObject> for SocketAddrV6 {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for SocketAddrV6 {
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        if let Some(addr) = parse(ob, "an IPv6 socket address")? {
+            return Ok(addr);
+        }
+        let tuple: &PyTuple = ob.downcast()?;
+        if tuple.len() == 2 {
+            let (ip, port) = tuple.extract()?;
+            Ok(SocketAddrV6::new(ip, port, 0, 0))
+        } else {
+            let (ip, port, flowinfo, scope_id) = tuple.extract()?;
+            Ok(SocketAddrV6::new(ip, port, flowinfo, scope_id))
+        }
+    }
+}
+
+impl ToPyObject for SocketAddr {
+    fn to_object(&self, py: Python) -> PyObject {
+        match self {
+            SocketAddr::V4(addr) => addr.to_object(py),
+            SocketAddr::V6(addr) => addr.to_object(py),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for SocketAddr {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for SocketAddr {
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        if let Some(addr) = parse(ob, "a socket address")? {
+            return Ok(addr);
+        }
+        let tuple: &PyTuple = ob.downcast()?;
+        if tuple.len() == 2 {
+            let (ip, port): (IpAddr, u16) = tuple.extract()?;
+            Ok(SocketAddr::new(ip, port))
+        } else {
+            SocketAddrV6::extract(tuple).map(SocketAddr::V6)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exceptions::PyTypeError;
+    use crate::py_run;
+
+    #[test]
+    fn test_ip_addr_roundtrip() {
+        Python::with_gil(|py| {
+            let v4 = Ipv4Addr::new(192, 168, 0, 1);
+            let obj = v4.to_object(py);
+            py_run!(
+                py,
+                obj,
+                "import ipaddress; assert obj == ipaddress.IPv4Address('192.168.0.1')"
+            );
+            assert_eq!(obj.extract::<Ipv4Addr>(py).unwrap(), v4);
+            assert_eq!(obj.extract::<IpAddr>(py).unwrap(), IpAddr::V4(v4));
+
+            let v6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+            let obj = IpAddr::V6(v6).into_py(py);
+            py_run!(
+                py,
+                obj,
+                "import ipaddress; assert obj == ipaddress.IPv6Address('2001:db8::1')"
+            );
+            assert_eq!(obj.extract::<Ipv6Addr>(py).unwrap(), v6);
+            assert_eq!(obj.extract::<IpAddr>(py).unwrap(), IpAddr::V6(v6));
+            assert!(obj.extract::<Ipv4Addr>(py).is_err());
+        });
+    }
+
+    #[test]
+    fn test_ip_addr_from_str() {
+        Python::with_gil(|py| {
+            let obj: PyObject = "10.0.0.1".into_py(py);
+            assert_eq!(
+                obj.extract::<IpAddr>(py).unwrap(),
+                IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))
+            );
+            let obj: PyObject = "::1".into_py(py);
+            assert_eq!(obj.extract::<Ipv6Addr>(py).unwrap(), Ipv6Addr::LOCALHOST);
+
+            let obj: PyObject = "not an address".into_py(py);
+            let err = obj.extract::<IpAddr>(py).unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+
+            let obj: PyObject = 1.into_py(py);
+            let err = obj.extract::<IpAddr>(py).unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_ip_addr_overridden_packed() {
+        Python::with_gil(|py| {
+            let globals = crate::types::PyDict::new(py);
+            py.run(
+                r#"
+import ipaddress
+class Odd(ipaddress.IPv4Address):
+    @property
+    def packed(self):
+        return b"\x01"
+odd = Odd("10.0.0.1")
+"#,
+                Some(globals),
+                None,
+            )
+            .unwrap();
+            let odd = globals.get_item("odd").unwrap();
+            let err = odd.extract::<Ipv4Addr>().unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+            assert!(odd.extract::<IpAddr>().is_err());
+        });
+    }
+
+    #[test]
+    fn test_socket_addr_roundtrip() {
+        Python::with_gil(|py| {
+            let v4: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+            let obj = v4.to_object(py);
+            py_run!(py, obj, "assert obj == ('127.0.0.1', 8080)");
+            assert_eq!(obj.extract::<SocketAddr>(py).unwrap(), v4);
+
+            let v6 = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 1, 2);
+            let obj = v6.to_object(py);
+            py_run!(py, obj, "assert obj == ('::1', 443, 1, 2)");
+            assert_eq!(obj.extract::<SocketAddr>(py).unwrap(), SocketAddr::V6(v6));
+        });
+    }
+
+    #[test]
+    fn test_socket_addr_from_python() {
+        Python::with_gil(|py| {
+            let obj = py
+                .eval(
+                    "(__import__('ipaddress').IPv4Address('1.2.3.4'), 80)",
+                    None,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(
+                obj.extract::<SocketAddrV4>().unwrap(),
+                SocketAddrV4::new(Ipv4Addr::new(1, 2, 3, 4), 80)
+            );
+            let obj: PyObject = "[::1]:22".into_py(py);
+            assert_eq!(
+                obj.extract::<SocketAddr>(py).unwrap(),
+                SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 22)
+            );
+            let obj: PyObject = ("::1", 22).into_py(py);
+            assert!(obj.extract::<SocketAddrV4>(py).is_err());
+        });
+    }
+}
@@ -0,0 +1,257 @@
+#![cfg(feature = "rust_decimal")]
+//! Conversions to and from [rust_decimal](https://docs.rs/rust_decimal)'s [`Decimal`] type.
+//!
+//! `Decimal` converts to and from Python's `decimal.Decimal`. The scale of the value is kept in
+//! both directions, so `1.50` stays `Decimal('1.50')`, and so is the sign of zero.
+//!
+//! Extracting a `decimal.Decimal` whose coefficient does not fit into the 96-bit mantissa of
+//! `Decimal` raises `OverflowError`. Values with more than 28 digits after the decimal point,
+//! as well as `NaN` and infinities, raise `ValueError`.
+//!
+//! # Setup
+//!
+//! To use this feature, add this to your **`Cargo.toml`**:
+//!
+//! ```toml
+//! [dependencies]
+//! # change * to the latest versions
+//! rust_decimal = "*"
+// workaround for `extended_key_value_attributes`: https://github.com/rust-lang/rust/issues/82768#issuecomment-803935643
+#![cfg_attr(docsrs, cfg_attr(docsrs, doc = concat!("pyo3 = { version = \"", env!("CARGO_PKG_VERSION"),  "\", features = [\"rust_decimal\"] }")))]
+#![cfg_attr(
+    not(docsrs),
+    doc = "pyo3 = { version = \"*\", features = [\"rust_decimal\"] }"
+)]
+//! ```
+//!
+//! Note that you must use compatible versions of rust_decimal and PyO3.
+//! The required rust_decimal version may vary based on the version of PyO3.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pyo3::types::IntoPyDict;
+//! use pyo3::{Python, ToPyObject};
+//! use rust_decimal::Decimal;
+//!
+//! fn main() {
+//!     pyo3::prepare_freethreaded_python();
+//!     Python::with_gil(|py| {
+//!         let price = Decimal::new(1999, 2);
+//!         let py_price = price.to_object(py);
+//!         assert_eq!(py_price.as_ref(py).str().unwrap().to_str().unwrap(), "19.99");
+//!
+//!         let locals = [("price", py_price)].into_py_dict(py);
+//!         let total: Decimal = py
+//!             .eval("price * 3", None, Some(locals))
+//!             .unwrap()
+//!             .extract()
+//!             .unwrap();
+//!         assert_eq!(total, Decimal::new(5997, 2));
+//!     });
+//! }
+//! ```
+//!
+//! [`Decimal`]: https://docs.rs/rust_decimal/latest/rust_decimal/struct.Decimal.html
+
+use crate::exceptions::{PyOverflowError, PyValueError};
+use crate::once_cell::GILOnceCell;
+use crate::types::{PyTuple, PyType};
+use crate::{
+    FromPyObject, IntoPy, Py, PyAny, PyDowncastError, PyErr, PyNativeType, PyObject, PyResult,
+    Python, ToPyObject,
+};
+use rust_decimal::Decimal;
+
+/// The largest scale `Decimal` can represent.
+const MAX_SCALE: i64 = 28;
+
+/// The largest coefficient `Decimal` can represent, `2**96 - 1`.
+const MAX_MANTISSA: u128 = (1 << 96) - 1;
+
+fn decimal_type(py: Python) -> PyResult<&PyType> {
+    static DECIMAL_TYPE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+    if let Some(ty) = DECIMAL_TYPE.get(py) {
+        return Ok(ty.as_ref(py));
+    }
+    let ty: Py<PyType> = py
+        .import("decimal")?
+        .getattr("Decimal")?
+        .downcast::<PyType>()
+        .map_err(PyErr::from)?
+        .into();
+    Ok(DECIMAL_TYPE.get_or_init(py, || ty).as_ref(py))
+}
+
+impl ToPyObject for Decimal {
+    fn to_object(&self, py: Python) -> PyObject {
+        // Build the value from its `(sign, digits, exponent)` tuple, which unlike the string
+        // representation always keeps the scale and the sign of zero.
+        let digits: Vec<u8> = self
+            .mantissa()
+            .abs()
+            .to_string()
+            .bytes()
+            .map(|digit| digit - b'0')
+            .collect();
+        let parts = (
+            self.is_sign_negative() as u8,
+            PyTuple::new(py, digits),
+            -(self.scale() as i64),
+        );
+        decimal_type(py)
+            .and_then(|ty| ty.call1((parts,)))
+            .expect("failed to create decimal.Decimal")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for Decimal {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for Decimal {
+    fn extract(obj: &PyAny) -> PyResult<Self> {
+        if !decimal_type(obj.py())?.is_instance(obj)? {
+            return Err(PyDowncastError::new(obj, "Decimal").into());
+        }
+        let (sign, digits, exponent): (u8, Vec<u8>, &PyAny) =
+            obj.call_method0("as_tuple")?.extract()?;
+        // `NaN`s and infinities have a string exponent
+        let exponent: i64 = exponent.extract().map_err(|_| {
+            PyValueError::new_err(format!(
+                "cannot convert {} to Decimal",
+                obj.str()
+                    .map_or_else(|_| "<unknown>".into(), |s| s.to_string_lossy().into_owned())
+            ))
+        })?;
+
+        let overflow = || PyOverflowError::new_err("value is out of range for Decimal");
+        let mut mantissa = 0u128;
+        for digit in digits {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|m| m.checked_add(u128::from(digit)))
+                .filter(|&m| m <= MAX_MANTISSA)
+                .ok_or_else(overflow)?;
+        }
+        let scale = if exponent > 0 {
+            for _ in 0..exponent {
+                if mantissa == 0 {
+                    break;
+                }
+                mantissa = mantissa
+                    .checked_mul(10)
+                    .filter(|&m| m <= MAX_MANTISSA)
+                    .ok_or_else(overflow)?;
+            }
+            0
+        } else if -exponent > MAX_SCALE {
+            return Err(PyValueError::new_err(format!(
+                "Decimal supports at most {} digits after the decimal point, got {}",
+                MAX_SCALE, -exponent
+            )));
+        } else {
+            -exponent as u32
+        };
+
+        let mut value = Decimal::from_parts(
+            mantissa as u32,
+            (mantissa >> 32) as u32,
+            (mantissa >> 64) as u32,
+            false,
+            scale,
+        );
+        // unlike `from_parts`, this keeps the sign of zero
+        value.set_sign_negative(sign != 0);
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exceptions::PyTypeError;
+    use crate::py_run;
+
+    fn py_decimal<'p>(py: Python<'p>, value: &str) -> &'p PyAny {
+        decimal_type(py).unwrap().call1((value,)).unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        Python::with_gil(|py| {
+            for value in &[
+                Decimal::new(1999, 2),
+                Decimal::new(-150, 2),
+                Decimal::new(0, 3),
+                Decimal::MAX,
+                Decimal::MIN,
+                Decimal::from_parts(1, 0, 0, false, 28),
+            ] {
+                let obj = value.to_object(py);
+                assert!(decimal_type(py)
+                    .unwrap()
+                    .is_instance(obj.as_ref(py))
+                    .unwrap());
+                let roundtrip: Decimal = obj.extract(py).unwrap();
+                assert_eq!(roundtrip, *value);
+                assert_eq!(roundtrip.scale(), value.scale());
+                assert_eq!(roundtrip.to_string(), value.to_string());
+            }
+        });
+    }
+
+    #[test]
+    fn test_to_python() {
+        Python::with_gil(|py| {
+            let obj = Decimal::new(-150, 2).to_object(py);
+            py_run!(py, obj, "assert str(obj) == '-1.50'");
+            let mut zero = Decimal::new(0, 1);
+            zero.set_sign_negative(true);
+            let zero = zero.to_object(py);
+            py_run!(py, zero, "assert str(zero) == '-0.0'");
+        });
+    }
+
+    #[test]
+    fn test_from_python() {
+        Python::with_gil(|py| {
+            let value: Decimal = py_decimal(py, "1.50").extract().unwrap();
+            assert_eq!(value.to_string(), "1.50");
+
+            let value: Decimal = py_decimal(py, "12E+3").extract().unwrap();
+            assert_eq!(value, Decimal::new(12000, 0));
+
+            let zero: Decimal = py_decimal(py, "-0.000").extract().unwrap();
+            assert!(zero.is_sign_negative());
+            assert_eq!(zero.scale(), 3);
+        });
+    }
+
+    #[test]
+    fn test_from_python_errors() {
+        Python::with_gil(|py| {
+            let err = py_decimal(py, "79228162514264337593543950336")
+                .extract::<Decimal>()
+                .unwrap_err();
+            assert!(err.is_instance::<PyOverflowError>(py));
+
+            let err = py_decimal(py, "1E+29").extract::<Decimal>().unwrap_err();
+            assert!(err.is_instance::<PyOverflowError>(py));
+
+            let err = py_decimal(py, "1E-29").extract::<Decimal>().unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+
+            let err = py_decimal(py, "NaN").extract::<Decimal>().unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+
+            let float: PyObject = 1.5.into_py(py);
+            let err = float.extract::<Decimal>(py).unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+        });
+    }
+}
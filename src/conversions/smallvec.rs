@@ -0,0 +1,96 @@
+#![cfg(feature = "smallvec")]
+//! Conversions to and from [smallvec](https://docs.rs/smallvec/)’s `SmallVec`.
+//!
+//! `SmallVec` converts to a Python `list`, and can be extracted from any sequence like `Vec`.
+//!
+//! # Setup
+//!
+//! To use this feature, add this to your **`Cargo.toml`**:
+//!
+//! ```toml
+//! [dependencies]
+//! # change * to the latest versions
+//! smallvec = "*"
+// workaround for `extended_key_value_attributes`: https://github.com/rust-lang/rust/issues/82768#issuecomment-803935643
+#![cfg_attr(docsrs, cfg_attr(docsrs, doc = concat!("pyo3 = { version = \"", env!("CARGO_PKG_VERSION"),  "\", features = [\"smallvec\"] }")))]
+#![cfg_attr(
+    not(docsrs),
+    doc = "pyo3 = { version = \"*\", features = [\"smallvec\"] }"
+)]
+//! ```
+//!
+//! Note that you must use compatible versions of smallvec and PyO3.
+//! The required smallvec version may vary based on the version of PyO3.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pyo3::prelude::*;
+//! use smallvec::SmallVec;
+//!
+//! #[pyfunction]
+//! fn double(values: SmallVec<[i64; 8]>) -> SmallVec<[i64; 8]> {
+//!     values.into_iter().map(|v| v * 2).collect()
+//! }
+//! ```
+
+use crate::types::{extract_sequence_into, PyList};
+use crate::{FromPyObject, IntoPy, PyAny, PyObject, PyResult, Python, ToPyObject};
+use smallvec::{Array, SmallVec};
+
+impl<A> ToPyObject for SmallVec<A>
+where
+    A: Array,
+    A::Item: ToPyObject,
+{
+    fn to_object(&self, py: Python) -> PyObject {
+        self.as_slice().to_object(py)
+    }
+}
+
+impl<A> IntoPy<PyObject> for SmallVec<A>
+where
+    A: Array,
+    A::Item: IntoPy<PyObject>,
+{
+    fn into_py(self, py: Python) -> PyObject {
+        PyList::new(py, self.into_iter().map(|e| e.into_py(py))).into()
+    }
+}
+
+impl<'a, A> FromPyObject<'a> for SmallVec<A>
+where
+    A: Array,
+    A::Item: FromPyObject<'a>,
+{
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        extract_sequence_into(obj, SmallVec::with_capacity, |v, item| {
+            v.push(item);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smallvec_roundtrip() {
+        Python::with_gil(|py| {
+            let v: SmallVec<[i32; 2]> = SmallVec::from_slice(&[1, 2, 3]);
+            let obj = v.to_object(py);
+            assert!(obj.as_ref(py).is_instance::<PyList>().unwrap());
+            assert_eq!(obj.extract::<SmallVec<[i32; 2]>>(py).unwrap(), v);
+            assert_eq!(
+                v.clone().into_py(py).extract::<Vec<i32>>(py).unwrap(),
+                [1, 2, 3]
+            );
+
+            let tuple = py.eval("(4, 5)", None, None).unwrap();
+            let v: SmallVec<[i32; 4]> = tuple.extract().unwrap();
+            assert_eq!(v.as_slice(), &[4, 5]);
+            assert!(!v.spilled());
+        });
+    }
+}
@@ -0,0 +1,151 @@
+//! Conversions for `Box`, `Rc`, `Arc` and `Cow`, which convert like the value they point to.
+//!
+//! Extracting a pointer to an unsized `str` or `[T]` goes through `String` and `Vec<T>`
+//! respectively, and `Box<T>` can only be extracted for these unsized types. An extracted
+//! `Cow<str>` borrows the Python string.
+use crate::types::PyString;
+use crate::{FromPyObject, IntoPy, PyAny, PyObject, PyResult, Python, ToPyObject};
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+macro_rules! smart_pointer_impl {
+    ($pointer:ident) => {
+        impl<T: ?Sized + ToPyObject> ToPyObject for $pointer<T> {
+            #[inline]
+            fn to_object(&self, py: Python) -> PyObject {
+                (**self).to_object(py)
+            }
+        }
+
+        impl<T: ?Sized + ToPyObject> IntoPy<PyObject> for $pointer<T> {
+            #[inline]
+            fn into_py(self, py: Python) -> PyObject {
+                (*self).to_object(py)
+            }
+        }
+
+        impl FromPyObject<'_> for $pointer<str> {
+            fn extract(ob: &PyAny) -> PyResult<Self> {
+                String::extract(ob).map(Into::into)
+            }
+        }
+    };
+}
+
+smart_pointer_impl!(Box);
+smart_pointer_impl!(Rc);
+smart_pointer_impl!(Arc);
+
+// There is no `FromPyObject` for `Box<T>`: as `Box` is a fundamental type, it would overlap
+// with the implementation for `T: PyClass + Clone`.
+
+impl<'source, T: FromPyObject<'source>> FromPyObject<'source> for Rc<T> {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        T::extract(ob).map(Rc::new)
+    }
+}
+
+impl<'source, T: FromPyObject<'source>> FromPyObject<'source> for Arc<T> {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        T::extract(ob).map(Arc::new)
+    }
+}
+
+impl<'source, T: FromPyObject<'source>> FromPyObject<'source> for Rc<[T]> {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        Vec::<T>::extract(ob).map(Into::into)
+    }
+}
+
+impl<'source, T: FromPyObject<'source>> FromPyObject<'source> for Arc<[T]> {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        Vec::<T>::extract(ob).map(Into::into)
+    }
+}
+
+impl<'a, T: Clone + ToPyObject> ToPyObject for Cow<'a, [T]> {
+    #[inline]
+    fn to_object(&self, py: Python) -> PyObject {
+        (**self).to_object(py)
+    }
+}
+
+impl<'a, T: Clone + ToPyObject> IntoPy<PyObject> for Cow<'a, [T]> {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl<'source, T: Clone + FromPyObject<'source>> FromPyObject<'source> for Cow<'_, [T]> {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        Vec::<T>::extract(ob).map(Cow::Owned)
+    }
+}
+
+impl<'source> FromPyObject<'source> for Cow<'source, str> {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        ob.downcast::<PyString>()?.to_str().map(Cow::Borrowed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{PyBytes, PyList};
+
+    #[test]
+    fn test_box_roundtrip() {
+        Python::with_gil(|py| {
+            let obj = Box::new(5u32).into_py(py);
+            assert_eq!(obj.extract::<u32>(py).unwrap(), 5);
+
+            let boxed_str: Box<str> = "hello".into();
+            let obj = boxed_str.to_object(py);
+            assert_eq!(obj.extract::<Box<str>>(py).unwrap(), boxed_str);
+        });
+    }
+
+    #[test]
+    fn test_rc_and_arc() {
+        Python::with_gil(|py| {
+            let obj = Rc::new("shared".to_owned()).into_py(py);
+            assert_eq!(&*obj.extract::<Rc<str>>(py).unwrap(), "shared");
+            assert_eq!(*obj.extract::<Arc<String>>(py).unwrap(), "shared");
+
+            let bytes = PyBytes::new(py, b"abc");
+            let arc: Arc<[u8]> = bytes.extract().unwrap();
+            assert_eq!(&*arc, b"abc");
+
+            let list = PyList::new(py, &[1, 2, 3]);
+            let rc: Rc<[i32]> = list.extract().unwrap();
+            assert_eq!(&*rc, &[1, 2, 3]);
+            let obj = Arc::<[i32]>::from(vec![4, 5]).to_object(py);
+            assert_eq!(obj.extract::<Vec<i32>>(py).unwrap(), vec![4, 5]);
+        });
+    }
+
+    #[test]
+    fn test_cow() {
+        Python::with_gil(|py| {
+            let slice: &[i32] = &[1, 2, 3];
+            let obj = Cow::Borrowed(slice).into_py(py);
+            assert!(obj.as_ref(py).is_instance::<PyList>().unwrap());
+            let cow: Cow<[i32]> = obj.extract(py).unwrap();
+            assert_eq!(&*cow, slice);
+
+            let obj = "text".to_object(py);
+            let cow: Cow<str> = obj.extract(py).unwrap();
+            assert!(matches_borrowed(&cow));
+            assert_eq!(cow, "text");
+        });
+    }
+
+    fn matches_borrowed(cow: &Cow<str>) -> bool {
+        match cow {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        }
+    }
+}
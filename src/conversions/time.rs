@@ -0,0 +1,362 @@
+//! Conversions between `std::time` types and the Python `datetime` module.
+//!
+//! [`Duration`] converts to and from `datetime.timedelta`, and [`SystemTime`] to and from a
+//! timezone-aware `datetime.datetime` in UTC. Under `abi3` the `datetime` C-API is unavailable,
+//! so the objects are created and inspected through the Python module instead.
+use crate::exceptions::{PyOverflowError, PyTypeError, PyValueError};
+use crate::once_cell::GILOnceCell;
+use crate::types::PyType;
+use crate::{
+    ffi, AsPyPointer, FromPyObject, IntoPy, PyAny, PyDowncastError, PyNativeType, PyObject,
+    PyResult, Python, ToPyObject,
+};
+#[cfg(Py_LIMITED_API)]
+use crate::{Py, PyErr};
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Converts a `Duration` to a `datetime.timedelta`.
+///
+/// # Panics
+///
+/// Panics if the `Duration` is longer than `timedelta.max`, 999999999 days.
+impl ToPyObject for Duration {
+    fn to_object(&self, py: Python) -> PyObject {
+        duration_to_timedelta(py, *self)
+            .expect("failed to convert Duration to timedelta")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for Duration {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for Duration {
+    fn extract(obj: &PyAny) -> PyResult<Self> {
+        if let Some((days, seconds, microseconds)) = timedelta_components(obj)? {
+            // `seconds` and `microseconds` are always normalized to be non-negative
+            let days = u64::try_from(days).map_err(|_| {
+                PyValueError::new_err("a negative timedelta cannot be converted to Duration")
+            })?;
+            return Ok(Duration::new(
+                days * SECONDS_PER_DAY + seconds as u64,
+                microseconds as u32 * 1000,
+            ));
+        }
+        seconds_to_duration(obj.extract()?)
+    }
+}
+
+/// Converts a `SystemTime` to an aware `datetime.datetime` in UTC.
+///
+/// # Panics
+///
+/// Panics if the `SystemTime` is outside the range of `datetime`, the years 1 to 9999.
+impl ToPyObject for SystemTime {
+    fn to_object(&self, py: Python) -> PyObject {
+        system_time_to_datetime(py, *self)
+            .expect("failed to convert SystemTime to datetime")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for SystemTime {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for SystemTime {
+    fn extract(obj: &PyAny) -> PyResult<Self> {
+        let py = obj.py();
+        if !datetime_type(py)?.is_instance(obj)? {
+            return Err(PyDowncastError::new(obj, "datetime").into());
+        }
+        // Subtracting an aware datetime raises `TypeError` for naive inputs
+        let since_epoch: &PyAny = unsafe {
+            py.from_owned_ptr_or_err(ffi::PyNumber_Subtract(
+                obj.as_ptr(),
+                utc_epoch(py)?.as_ptr(),
+            ))?
+        };
+        // A subclass of `datetime` may override subtraction to return something else
+        let (days, seconds, microseconds) =
+            timedelta_components(since_epoch)?.ok_or_else(|| {
+                PyTypeError::new_err(
+                    "subtracting the Unix epoch from the datetime did not produce a timedelta",
+                )
+            })?;
+        let micros = (i128::from(days) * i128::from(SECONDS_PER_DAY) + i128::from(seconds))
+            * 1_000_000
+            + i128::from(microseconds);
+        let offset =
+            Duration::from_micros(u64::try_from(micros.abs()).map_err(|_| {
+                PyOverflowError::new_err("datetime is out of range for SystemTime")
+            })?);
+        let time = if micros >= 0 {
+            UNIX_EPOCH.checked_add(offset)
+        } else {
+            UNIX_EPOCH.checked_sub(offset)
+        };
+        time.ok_or_else(|| PyOverflowError::new_err("datetime is out of range for SystemTime"))
+    }
+}
+
+fn seconds_to_duration(seconds: f64) -> PyResult<Duration> {
+    if seconds.is_nan() {
+        Err(PyValueError::new_err(
+            "NaN seconds cannot be converted to Duration",
+        ))
+    } else if seconds < 0.0 {
+        Err(PyValueError::new_err(
+            "a negative number of seconds cannot be converted to Duration",
+        ))
+    } else if seconds >= u64::MAX as f64 {
+        Err(PyOverflowError::new_err(
+            "seconds are out of range for Duration",
+        ))
+    } else {
+        Ok(Duration::from_secs_f64(seconds))
+    }
+}
+
+fn duration_to_timedelta(py: Python, duration: Duration) -> PyResult<&PyAny> {
+    let days = i32::try_from(duration.as_secs() / SECONDS_PER_DAY)
+        .map_err(|_| PyOverflowError::new_err("Duration is out of range for timedelta"))?;
+    let seconds = (duration.as_secs() % SECONDS_PER_DAY) as i32;
+    let microseconds = duration.subsec_micros() as i32;
+    new_timedelta(py, days, seconds, microseconds)
+}
+
+fn system_time_to_datetime(py: Python, time: SystemTime) -> PyResult<&PyAny> {
+    let epoch = utc_epoch(py)?;
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => epoch.call_method1("__add__", (duration_to_timedelta(py, after)?,)),
+        Err(before) => {
+            epoch.call_method1("__sub__", (duration_to_timedelta(py, before.duration())?,))
+        }
+    }
+}
+
+/// `datetime.datetime(1970, 1, 1, tzinfo=datetime.timezone.utc)`
+fn utc_epoch(py: Python) -> PyResult<&PyAny> {
+    static UTC_EPOCH: GILOnceCell<PyObject> = GILOnceCell::new();
+    if let Some(epoch) = UTC_EPOCH.get(py) {
+        return Ok(epoch.as_ref(py));
+    }
+    let datetime = py.import("datetime")?;
+    let utc = datetime.getattr("timezone")?.getattr("utc")?;
+    let epoch: PyObject = datetime_type(py)?
+        .call1((1970, 1, 1, 0, 0, 0, 0, utc))?
+        .into();
+    Ok(UTC_EPOCH.get_or_init(py, || epoch).as_ref(py))
+}
+
+#[cfg(not(Py_LIMITED_API))]
+fn datetime_type(py: Python) -> PyResult<&PyType> {
+    Ok(py.get_type::<crate::types::PyDateTime>())
+}
+
+#[cfg(not(Py_LIMITED_API))]
+fn new_timedelta(py: Python, days: i32, seconds: i32, microseconds: i32) -> PyResult<&PyAny> {
+    crate::types::PyDelta::new(py, days, seconds, microseconds, false).map(Into::into)
+}
+
+/// Returns the `(days, seconds, microseconds)` of `obj` if it is a `timedelta`.
+#[cfg(not(Py_LIMITED_API))]
+fn timedelta_components(obj: &PyAny) -> PyResult<Option<(i32, i32, i32)>> {
+    use crate::types::{PyDelta, PyDeltaAccess};
+    Ok(obj.downcast::<PyDelta>().ok().map(|delta| {
+        (
+            delta.get_days(),
+            delta.get_seconds(),
+            delta.get_microseconds(),
+        )
+    }))
+}
+
+#[cfg(Py_LIMITED_API)]
+fn datetime_type(py: Python) -> PyResult<&PyType> {
+    static DATETIME: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+    import_type(py, &DATETIME, "datetime")
+}
+
+#[cfg(Py_LIMITED_API)]
+fn timedelta_type(py: Python) -> PyResult<&PyType> {
+    static TIMEDELTA: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+    import_type(py, &TIMEDELTA, "timedelta")
+}
+
+#[cfg(Py_LIMITED_API)]
+fn import_type<'py>(
+    py: Python<'py>,
+    cell: &'py GILOnceCell<Py<PyType>>,
+    name: &str,
+) -> PyResult<&'py PyType> {
+    if let Some(ty) = cell.get(py) {
+        return Ok(ty.as_ref(py));
+    }
+    let ty: Py<PyType> = py
+        .import("datetime")?
+        .getattr(name)?
+        .downcast::<PyType>()
+        .map_err(PyErr::from)?
+        .into();
+    Ok(cell.get_or_init(py, || ty).as_ref(py))
+}
+
+#[cfg(Py_LIMITED_API)]
+fn new_timedelta(py: Python, days: i32, seconds: i32, microseconds: i32) -> PyResult<&PyAny> {
+    timedelta_type(py)?.call1((days, seconds, microseconds))
+}
+
+#[cfg(Py_LIMITED_API)]
+fn timedelta_components(obj: &PyAny) -> PyResult<Option<(i32, i32, i32)>> {
+    if !timedelta_type(obj.py())?.is_instance(obj)? {
+        return Ok(None);
+    }
+    Ok(Some((
+        obj.getattr("days")?.extract()?,
+        obj.getattr("seconds")?.extract()?,
+        obj.getattr("microseconds")?.extract()?,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exceptions::{PyTypeError, PyValueError};
+    use crate::{IntoPy, PyObject, Python, ToPyObject};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn test_duration_roundtrip() {
+        Python::with_gil(|py| {
+            let duration = Duration::new(2 * 86400 + 3, 4_000);
+            let obj = duration.to_object(py);
+            crate::py_run!(
+                py,
+                obj,
+                "import datetime; assert obj == datetime.timedelta(days=2, seconds=3, microseconds=4)"
+            );
+            let roundtrip: Duration = duration.into_py(py).extract(py).unwrap();
+            assert_eq!(roundtrip, duration);
+        });
+    }
+
+    #[test]
+    fn test_duration_from_number() {
+        Python::with_gil(|py| {
+            let float: PyObject = 1.5.into_py(py);
+            assert_eq!(
+                float.extract::<Duration>(py).unwrap(),
+                Duration::from_millis(1500)
+            );
+            let int: PyObject = 3.into_py(py);
+            assert_eq!(int.extract::<Duration>(py).unwrap(), Duration::from_secs(3));
+        });
+    }
+
+    #[test]
+    fn test_duration_rejects_negative() {
+        Python::with_gil(|py| {
+            let delta = py.eval(
+                "__import__('datetime').timedelta(microseconds=-1)",
+                None,
+                None,
+            );
+            let err = delta.unwrap().extract::<Duration>().unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+
+            let float: PyObject = (-0.5).into_py(py);
+            let err = float.extract::<Duration>(py).unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+
+            let string: PyObject = "1".into_py(py);
+            let err = string.extract::<Duration>(py).unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+
+            let nan: PyObject = f64::NAN.into_py(py);
+            let err = nan.extract::<Duration>(py).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "ValueError: NaN seconds cannot be converted to Duration"
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to convert Duration to timedelta")]
+    fn test_duration_out_of_range_panics() {
+        Python::with_gil(|py| {
+            Duration::from_secs(u64::MAX).to_object(py);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to convert SystemTime to datetime")]
+    fn test_system_time_out_of_range_panics() {
+        Python::with_gil(|py| {
+            let year_10000 = UNIX_EPOCH + Duration::from_secs(253_402_300_800);
+            year_10000.to_object(py);
+        });
+    }
+
+    #[test]
+    fn test_system_time_roundtrip() {
+        Python::with_gil(|py| {
+            let time = UNIX_EPOCH + Duration::new(1_000_000_000, 123_000);
+            let obj = time.to_object(py);
+            crate::py_run!(
+                py,
+                obj,
+                "import datetime; assert obj == datetime.datetime(2001, 9, 9, 1, 46, 40, 123, tzinfo=datetime.timezone.utc)"
+            );
+            let roundtrip: SystemTime = obj.extract(py).unwrap();
+            assert_eq!(roundtrip, time);
+
+            let before_epoch = UNIX_EPOCH - Duration::new(86400, 500_000);
+            let roundtrip: SystemTime = before_epoch.into_py(py).extract(py).unwrap();
+            assert_eq!(roundtrip, before_epoch);
+        });
+    }
+
+    #[test]
+    fn test_system_time_rejects_naive() {
+        Python::with_gil(|py| {
+            let naive = py
+                .eval("__import__('datetime').datetime(2000, 1, 1)", None, None)
+                .unwrap();
+            let err = naive.extract::<SystemTime>().unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_system_time_rejects_odd_subtraction() {
+        Python::with_gil(|py| {
+            let locals = crate::types::PyDict::new(py);
+            py.run(
+                r#"
+import datetime
+class Odd(datetime.datetime):
+    def __sub__(self, other):
+        return 42
+odd = Odd(2000, 1, 1, tzinfo=datetime.timezone.utc)
+"#,
+                None,
+                Some(locals),
+            )
+            .unwrap();
+            let odd = locals.get_item("odd").unwrap();
+            let err = odd.extract::<SystemTime>().unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+        });
+    }
+}
@@ -0,0 +1,148 @@
+#![cfg(feature = "uuid")]
+//! Conversions to and from [uuid](https://docs.rs/uuid)'s [`Uuid`] type.
+//!
+//! `Uuid` converts to Python's `uuid.UUID`. It can be extracted from a `uuid.UUID`, or from a
+//! `bytes` object of length 16 holding the UUID in big-endian order, as returned by
+//! `UUID.bytes`.
+//!
+//! # Setup
+//!
+//! To use this feature, add this to your **`Cargo.toml`**:
+//!
+//! ```toml
+//! [dependencies]
+//! # change * to the latest versions
+//! uuid = "*"
+// workaround for `extended_key_value_attributes`: https://github.com/rust-lang/rust/issues/82768#issuecomment-803935643
+#![cfg_attr(docsrs, cfg_attr(docsrs, doc = concat!("pyo3 = { version = \"", env!("CARGO_PKG_VERSION"),  "\", features = [\"uuid\"] }")))]
+#![cfg_attr(not(docsrs), doc = "pyo3 = { version = \"*\", features = [\"uuid\"] }")]
+//! ```
+//!
+//! Note that you must use compatible versions of uuid and PyO3.
+//! The required uuid version may vary based on the version of PyO3.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pyo3::{Python, ToPyObject};
+//! use uuid::Uuid;
+//!
+//! fn main() {
+//!     pyo3::prepare_freethreaded_python();
+//!     Python::with_gil(|py| {
+//!         let id = Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8);
+//!         let py_id = id.to_object(py);
+//!         assert_eq!(
+//!             py_id.as_ref(py).str().unwrap().to_str().unwrap(),
+//!             "67e55044-10b1-426f-9247-bb680e5fe0c8"
+//!         );
+//!         assert_eq!(py_id.extract::<Uuid>(py).unwrap(), id);
+//!     });
+//! }
+//! ```
+//!
+//! [`Uuid`]: https://docs.rs/uuid/latest/uuid/struct.Uuid.html
+
+use crate::once_cell::GILOnceCell;
+use crate::types::{IntoPyDict, PyBytes, PyType};
+use crate::{
+    FromPyObject, IntoPy, Py, PyAny, PyDowncastError, PyErr, PyNativeType, PyObject, PyResult,
+    Python, ToPyObject,
+};
+use std::convert::TryFrom;
+use uuid::Uuid;
+
+fn uuid_type(py: Python) -> PyResult<&PyType> {
+    static UUID_TYPE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+    if let Some(ty) = UUID_TYPE.get(py) {
+        return Ok(ty.as_ref(py));
+    }
+    let ty: Py<PyType> = py
+        .import("uuid")?
+        .getattr("UUID")?
+        .downcast::<PyType>()
+        .map_err(PyErr::from)?
+        .into();
+    Ok(UUID_TYPE.get_or_init(py, || ty).as_ref(py))
+}
+
+impl ToPyObject for Uuid {
+    fn to_object(&self, py: Python) -> PyObject {
+        let kwargs = [("int", self.as_u128())].into_py_dict(py);
+        uuid_type(py)
+            .and_then(|ty| ty.call((), Some(kwargs)))
+            .expect("failed to create uuid.UUID")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for Uuid {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl FromPyObject<'_> for Uuid {
+    fn extract(obj: &PyAny) -> PyResult<Self> {
+        if uuid_type(obj.py())?.is_instance(obj)? {
+            return obj.getattr("int")?.extract().map(Uuid::from_u128);
+        }
+        if let Ok(bytes) = obj.downcast::<PyBytes>() {
+            if let Ok(bytes) = <[u8; 16]>::try_from(bytes.as_bytes()) {
+                return Ok(Uuid::from_bytes(bytes));
+            }
+        }
+        Err(PyDowncastError::new(obj, "UUID").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exceptions::PyTypeError;
+    use crate::py_run;
+
+    #[test]
+    fn test_roundtrip() {
+        Python::with_gil(|py| {
+            let id = Uuid::from_u128(0xa1a2a3a4_b1b2_c1c2_d1d2_d3d4d5d6d7d8);
+            let obj = id.to_object(py);
+            py_run!(
+                py,
+                obj,
+                "import uuid; assert obj == uuid.UUID('a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8')"
+            );
+            assert_eq!(obj.extract::<Uuid>(py).unwrap(), id);
+            assert_eq!(
+                Uuid::nil().into_py(py).extract::<Uuid>(py).unwrap(),
+                Uuid::nil()
+            );
+        });
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        Python::with_gil(|py| {
+            let bytes = py
+                .eval(
+                    "__import__('uuid').UUID('a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8').bytes",
+                    None,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(
+                bytes.extract::<Uuid>().unwrap(),
+                Uuid::from_u128(0xa1a2a3a4_b1b2_c1c2_d1d2_d3d4d5d6d7d8)
+            );
+
+            let short = PyBytes::new(py, &[0; 15]);
+            let err = short.extract::<Uuid>().unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+
+            let string: PyObject = "a1a2a3a4-b1b2-c1c2-d1d2-d3d4d5d6d7d8".into_py(py);
+            let err = string.extract::<Uuid>(py).unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+        });
+    }
+}
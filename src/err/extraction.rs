@@ -0,0 +1,182 @@
+//! Error type recording where a nested extraction failed.
+use crate::exceptions::PyTypeError;
+use crate::types::PyTuple;
+use crate::{IntoPy, PyErr, PyObject, PyResult, Python};
+use std::fmt;
+
+pyo3_exception!(
+    "
+    The exception raised when extracting a field of a `#[derive(FromPyObject)]` type fails.
+
+    The path to the failing field is available as the `path` attribute, a tuple of field names
+    and sequence indices, and the type the path starts from as the `type_name` attribute. The
+    original error is set as `__cause__`.
+
+    When no variant of an enum could be extracted, the path is empty and the errors of the
+    variants are available as the `variant_errors` attribute instead.
+    ",
+    ExtractionError,
+    PyTypeError
+);
+
+/// A single step of the path recorded in an [`ExtractionError`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// A named field, or the position of a field in a tuple struct.
+    Field(String),
+    /// An element of a sequence.
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, ".{}", name),
+            PathSegment::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+impl IntoPy<PyObject> for PathSegment {
+    fn into_py(self, py: Python) -> PyObject {
+        match self {
+            PathSegment::Field(name) => name.into_py(py),
+            PathSegment::Index(index) => index.into_py(py),
+        }
+    }
+}
+
+impl ExtractionError {
+    /// Creates an error for the failed extraction of `field` of the type `type_name`.
+    ///
+    /// If `error` is itself an `ExtractionError`, its path is appended to `field` so that the
+    /// resulting error points at the innermost failing field, e.g.
+    /// `failed to extract field Config.servers[3].port: ...`. Otherwise `error` becomes the cause.
+    pub fn new_field_err(py: Python, type_name: &str, field: &str, error: PyErr) -> PyErr {
+        let (mut path, cause) = Self::split(py, error);
+        path.insert(0, PathSegment::Field(field.to_owned()));
+        Self::build(py, Some(type_name), path, cause)
+    }
+
+    /// Prepends the position of a sequence element to the path of an `ExtractionError`.
+    ///
+    /// Other errors are returned unchanged.
+    pub fn with_index(py: Python, index: usize, error: PyErr) -> PyErr {
+        if !error.is_instance::<ExtractionError>(py) {
+            return error;
+        }
+        let (mut path, cause) = Self::split(py, error);
+        path.insert(0, PathSegment::Index(index));
+        Self::build(py, None, path, cause)
+    }
+
+    /// Replaces the type the path of an `ExtractionError` starts from, used by transparent
+    /// containers which do not add a path segment of their own.
+    ///
+    /// Other errors are returned unchanged.
+    #[doc(hidden)]
+    pub fn with_type_name(py: Python, type_name: &str, error: PyErr) -> PyErr {
+        if !error.is_instance::<ExtractionError>(py) {
+            return error;
+        }
+        let (path, cause) = Self::split(py, error);
+        Self::build(py, Some(type_name), path, cause)
+    }
+
+    /// Creates the error for the enum `type_name` when none of its variants could be extracted.
+    ///
+    /// `expected` describes the accepted variants, e.g. `Union[str, int]`. The error of each
+    /// variant is kept as it is, including its path, and returned by
+    /// [`variant_errors`](ExtractionError::variant_errors).
+    pub fn new_enum_err(
+        py: Python,
+        type_name: &str,
+        expected: &str,
+        variant_errors: Vec<PyErr>,
+    ) -> PyErr {
+        let mut msg = format!("failed to extract enum {} ('{}')\n", type_name, expected);
+        for error in &variant_errors {
+            match error.instance(py).str() {
+                Ok(reason) => msg.push_str(&reason.to_string_lossy()),
+                Err(_) => msg.push_str("<exception str() failed>"),
+            }
+            msg.push('\n');
+        }
+
+        let error = ExtractionError::new_err((msg,));
+        let instance = error.instance(py);
+        let variant_errors = PyTuple::new(
+            py,
+            variant_errors.into_iter().map(|error| error.into_py(py)),
+        );
+        if let Err(err) = instance
+            .setattr("type_name", type_name)
+            .and_then(|_| instance.setattr("path", PyTuple::empty(py)))
+            .and_then(|_| instance.setattr("variant_errors", variant_errors))
+        {
+            return err;
+        }
+        error
+    }
+
+    /// Returns the name of the type the path starts from, if known.
+    pub fn type_name(&self) -> PyResult<Option<String>> {
+        self.getattr("type_name")?.extract()
+    }
+
+    /// Returns the path from the outermost type to the field which failed to extract.
+    pub fn path(&self) -> PyResult<Vec<PathSegment>> {
+        let path: &PyTuple = self.getattr("path")?.downcast()?;
+        path.iter()
+            .map(|segment| match segment.extract::<usize>() {
+                Ok(index) => Ok(PathSegment::Index(index)),
+                Err(_) => segment.extract().map(PathSegment::Field),
+            })
+            .collect()
+    }
+
+    /// Returns the errors of the variants of an enum which failed to extract, in declaration
+    /// order. Empty for errors of fields.
+    pub fn variant_errors(&self) -> PyResult<Vec<PyErr>> {
+        if !self.hasattr("variant_errors")? {
+            return Ok(Vec::new());
+        }
+        let errors: &PyTuple = self.getattr("variant_errors")?.downcast()?;
+        Ok(errors.iter().map(PyErr::from_instance).collect())
+    }
+
+    /// Splits `error` into the path it carries and the error which caused it.
+    fn split(py: Python, error: PyErr) -> (Vec<PathSegment>, PyErr) {
+        if let Ok(extraction_error) = error.instance(py).downcast::<ExtractionError>() {
+            if let (Ok(path), Some(cause)) = (extraction_error.path(), error.cause(py)) {
+                return (path, cause);
+            }
+        }
+        (Vec::new(), error)
+    }
+
+    fn build(py: Python, type_name: Option<&str>, path: Vec<PathSegment>, cause: PyErr) -> PyErr {
+        let mut location: String = path.iter().map(PathSegment::to_string).collect();
+        match type_name {
+            Some(type_name) => location.insert_str(0, type_name),
+            None => location = location.trim_start_matches('.').to_owned(),
+        }
+        let mut msg = format!("failed to extract field {}: ", location);
+        match cause.instance(py).str() {
+            Ok(reason) => msg.push_str(&reason.to_string_lossy()),
+            Err(_) => msg.push_str("<exception str() failed>"),
+        }
+
+        let error = ExtractionError::new_err((msg,));
+        let instance = error.instance(py);
+        let path = PyTuple::new(py, path.into_iter().map(|segment| segment.into_py(py)));
+        if let Err(err) = instance
+            .setattr("type_name", type_name)
+            .and_then(|_| instance.setattr("path", path))
+        {
+            return err;
+        }
+        error.set_cause(py, Some(cause));
+        error
+    }
+}
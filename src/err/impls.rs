@@ -8,9 +8,13 @@ impl std::convert::From<PyErr> for io::Error {
     }
 }
 
-/// Create `OSError` from `io::Error`
+/// Create `OSError` from `io::Error`, or return the `PyErr` that the `io::Error` wraps
 impl std::convert::From<io::Error> for PyErr {
     fn from(err: io::Error) -> PyErr {
+        if err.get_ref().map_or(false, |inner| inner.is::<PyErr>()) {
+            let inner = err.into_inner().expect("checked above");
+            return *inner.downcast::<PyErr>().expect("checked above");
+        }
         match err.kind() {
             io::ErrorKind::BrokenPipe => exceptions::PyBrokenPipeError::new_err(err),
             io::ErrorKind::ConnectionRefused => exceptions::PyConnectionRefusedError::new_err(err),
@@ -86,6 +90,48 @@ impl_to_pyerr!(
 );
 impl_to_pyerr!(std::net::AddrParseError, exceptions::PyValueError);
 
+/// Converts the messages of an error and its chain of sources into a `RuntimeError` whose
+/// `__cause__` is a `RuntimeError` for the next source, and so on.
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+fn error_chain_to_pyerr(messages: Vec<String>) -> PyErr {
+    Python::with_gil(|py| {
+        let mut error: Option<PyErr> = None;
+        for message in messages.into_iter().rev() {
+            let outer = exceptions::PyRuntimeError::new_err(message);
+            if error.is_some() {
+                outer.set_cause(py, error);
+            }
+            error = Some(outer);
+        }
+        error.expect("an error chain contains at least one error")
+    })
+}
+
+/// Implements `From<$report> for PyErr` for an error report type such as `anyhow::Error`.
+///
+/// A `PyErr` wrapped into the report is returned as it was. Other errors raise `RuntimeError`
+/// with the report's message, with the messages of the underlying errors as `__cause__`.
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+macro_rules! impl_report_to_pyerr {
+    ($report: ty) => {
+        impl std::convert::From<$report> for PyErr {
+            fn from(report: $report) -> PyErr {
+                match report.downcast::<PyErr>() {
+                    Ok(err) => err,
+                    Err(report) => {
+                        error_chain_to_pyerr(report.chain().map(|e| e.to_string()).collect())
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "anyhow")]
+impl_report_to_pyerr!(anyhow::Error);
+#[cfg(feature = "eyre")]
+impl_report_to_pyerr!(eyre::Report);
+
 #[cfg(test)]
 mod tests {
     use crate::PyErr;
@@ -111,4 +157,64 @@ mod tests {
         check_err(io::ErrorKind::WouldBlock, "BlockingIOError");
         check_err(io::ErrorKind::TimedOut, "TimeoutError");
     }
+
+    #[test]
+    fn io_error_wrapping_pyerr() {
+        use crate::exceptions::PyBufferError;
+        use crate::Python;
+        Python::with_gil(|py| {
+            let err = io::Error::new(io::ErrorKind::Other, PyBufferError::new_err("exported"));
+            let py_err: PyErr = err.into();
+            assert!(py_err.is_instance::<PyBufferError>(py));
+            assert_eq!(py_err.to_string(), "BufferError: exported");
+        });
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn anyhow_errors() {
+        use crate::exceptions::{PyRuntimeError, PyValueError};
+        use crate::Python;
+        use anyhow::Context;
+
+        Python::with_gil(|py| {
+            let result: anyhow::Result<()> = Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+                .context("failed to write cache")
+                .context("failed to save");
+            let err = PyErr::from(result.unwrap_err());
+            assert!(err.is_instance::<PyRuntimeError>(py));
+            assert_eq!(err.to_string(), "RuntimeError: failed to save");
+            let cause = err.cause(py).unwrap();
+            assert_eq!(cause.to_string(), "RuntimeError: failed to write cache");
+            let root = cause.cause(py).unwrap();
+            assert_eq!(root.to_string(), "RuntimeError: disk full");
+            assert!(root.cause(py).is_none());
+
+            let py_err = PyValueError::new_err("bad value");
+            let err = PyErr::from(anyhow::Error::from(py_err));
+            assert!(err.is_instance::<PyValueError>(py));
+            assert_eq!(err.to_string(), "ValueError: bad value");
+        });
+    }
+
+    #[cfg(feature = "eyre")]
+    #[test]
+    fn eyre_errors() {
+        use crate::exceptions::{PyRuntimeError, PyValueError};
+        use crate::Python;
+        use eyre::WrapErr;
+
+        Python::with_gil(|py| {
+            let result: eyre::Result<()> =
+                Err(io::Error::new(io::ErrorKind::Other, "disk full")).wrap_err("failed to save");
+            let err = PyErr::from(result.unwrap_err());
+            assert!(err.is_instance::<PyRuntimeError>(py));
+            assert_eq!(err.to_string(), "RuntimeError: failed to save");
+            let cause = err.cause(py).unwrap();
+            assert_eq!(cause.to_string(), "RuntimeError: disk full");
+
+            let err = PyErr::from(eyre::Report::new(PyValueError::new_err("bad value")));
+            assert!(err.is_instance::<PyValueError>(py));
+        });
+    }
 }
@@ -8,8 +8,8 @@ use crate::{
     ffi,
 };
 use crate::{
-    AsPyPointer, FromPyPointer, IntoPy, Py, PyAny, PyNativeType, PyObject, Python,
-    ToBorrowedObject, ToPyObject,
+    AsPyPointer, FromPyPointer, IntoPy, Py, PyAny, PyCell, PyClass, PyClassInitializer,
+    PyNativeType, PyObject, PyTryFrom, Python, ToBorrowedObject, ToPyObject,
 };
 use std::borrow::Cow;
 use std::cell::UnsafeCell;
@@ -19,10 +19,12 @@ use std::os::raw::c_int;
 use std::ptr::NonNull;
 
 mod err_state;
+mod extraction;
 mod impls;
 
 pub use err_state::PyErrArguments;
 use err_state::{boxed_args, PyErrState, PyErrStateNormalized};
+pub use extraction::{ExtractionError, PathSegment};
 
 /// Represents a Python exception that was raised.
 pub struct PyErr {
@@ -365,6 +367,15 @@ impl PyErr {
         self.normalized(py).pvalue.as_ref(py)
     }
 
+    /// Retrieves the exception instance for this error as an instance of the `#[pyclass]` `T`,
+    /// which gives access to the Rust data of exceptions created with
+    /// `#[pyclass(extends=PyException)]`.
+    ///
+    /// Returns `None` if the exception instance is not an instance of `T`.
+    pub fn instance_of<'py, T: PyClass>(&'py self, py: Python<'py>) -> Option<&'py PyCell<T>> {
+        <PyCell<T> as PyTryFrom>::try_from(self.instance(py)).ok()
+    }
+
     /// Consumes self to take ownership of the exception instance for this error.
     pub fn into_instance(self, py: Python) -> Py<PyBaseException> {
         let out = self.normalized(py).pvalue.as_ref(py).into();
@@ -540,6 +551,19 @@ impl<'a> IntoPy<PyObject> for &'a PyErr {
     }
 }
 
+/// Create an error from an initializer for a `#[pyclass]` which extends an exception type.
+///
+/// Like [`PyErr::from_instance`], this produces a `TypeError` if the class does not derive from
+/// `BaseException`.
+impl<T: PyClass> std::convert::From<PyClassInitializer<T>> for PyErr {
+    fn from(init: PyClassInitializer<T>) -> PyErr {
+        Python::with_gil(|py| match Py::new(py, init) {
+            Ok(obj) => PyErr::from_instance(obj.as_ref(py)),
+            Err(err) => err,
+        })
+    }
+}
+
 /// Convert `PyDowncastError` to Python `TypeError`.
 impl<'a> std::convert::From<PyDowncastError<'a>> for PyErr {
     fn from(err: PyDowncastError) -> PyErr {
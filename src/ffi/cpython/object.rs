@@ -186,7 +186,21 @@ mod typeobject {
         pub mp_ass_subscript: Option<object::objobjargproc>,
     }
 
-    // skipped PySendResult
+    #[cfg(Py_3_10)]
+    #[repr(C)]
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum PySendResult {
+        PYGEN_RETURN = 0,
+        PYGEN_ERROR = -1,
+        PYGEN_NEXT = 1,
+    }
+
+    #[cfg(Py_3_10)]
+    pub type sendfunc = unsafe extern "C" fn(
+        iter: *mut PyObject,
+        value: *mut PyObject,
+        result: *mut *mut PyObject,
+    ) -> PySendResult;
 
     #[repr(C)]
     #[derive(Clone, Default)]
@@ -194,6 +208,8 @@ mod typeobject {
         pub am_await: Option<object::unaryfunc>,
         pub am_aiter: Option<object::unaryfunc>,
         pub am_anext: Option<object::unaryfunc>,
+        #[cfg(Py_3_10)]
+        pub am_send: Option<sendfunc>,
     }
 
     #[repr(C)]
@@ -271,6 +287,10 @@ mod typeobject {
         pub tp_finalize: Option<object::destructor>,
         #[cfg(Py_3_8)]
         pub tp_vectorcall: Option<super::vectorcallfunc>,
+        #[cfg(Py_3_12)]
+        pub tp_watched: std::os::raw::c_uchar,
+        #[cfg(Py_3_13)]
+        pub tp_versions_used: u16,
         #[cfg(PyPy)]
         pub tp_pypy_flags: std::os::raw::c_long,
         #[cfg(py_sys_config = "COUNT_ALLOCS")]
@@ -298,6 +318,23 @@ mod typeobject {
         pub ht_slots: *mut object::PyObject,
         pub ht_qualname: *mut object::PyObject,
         pub ht_cached_keys: *mut c_void,
+        #[cfg(Py_3_9)]
+        pub ht_module: *mut object::PyObject,
+        #[cfg(Py_3_10)]
+        pub _ht_tpname: *mut c_char,
+        #[cfg(Py_3_11)]
+        pub _spec_cache: _specialization_cache,
+    }
+
+    #[cfg(Py_3_11)]
+    #[repr(C)]
+    #[derive(Clone)]
+    pub struct _specialization_cache {
+        pub getitem: *mut object::PyObject,
+        #[cfg(Py_3_12)]
+        pub getitem_version: u32,
+        #[cfg(Py_3_13)]
+        pub init: *mut object::PyObject,
     }
 
     impl Default for PyHeapTypeObject {
@@ -312,7 +349,7 @@ mod typeobject {
         etype: *mut PyHeapTypeObject,
     ) -> *mut ffi::structmember::PyMemberDef {
         let py_type = object::Py_TYPE(etype as *mut object::PyObject);
-        let ptr = etype.offset((*py_type).tp_basicsize);
+        let ptr = (etype as *mut u8).offset((*py_type).tp_basicsize);
         ptr as *mut ffi::structmember::PyMemberDef
     }
 }
@@ -61,14 +61,34 @@
 //! - `abi3`: Restricts PyO3's API to a subset of the full Python API which is guaranteed
 //! by [PEP 384](https://www.python.org/dev/peps/pep-0384/) to be forward-compatible with future Python versions.
 //
+//! - `anyhow`: Implements `From<anyhow::Error>` for [`PyErr`], so that functions can return
+//! [anyhow](https://docs.rs/anyhow)'s `Result`.
+//
+//! - [`arrayvec`](crate::arrayvec): Enables conversions between Python sequences and
+//! [arrayvec](https://docs.rs/arrayvec)'s
+//! [`ArrayVec`](https://docs.rs/arrayvec/latest/arrayvec/struct.ArrayVec.html).
+//
 //! - `auto-initialize`: Changes [`Python::with_gil`](crate::Python::with_gil) and
 //! [`Python::acquire_gil`](crate::Python::acquire_gil) to automatically initialize the
 //! Python interpreter if needed.
 //
+//! - [`chrono`](crate::chrono): Enables conversions between Python's `datetime` types and
+//! [chrono](https://docs.rs/chrono)'s
+//! [`NaiveDate`](https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDate.html),
+//! [`DateTime`](https://docs.rs/chrono/latest/chrono/struct.DateTime.html),
+//! [`Duration`](https://docs.rs/chrono/latest/chrono/struct.Duration.html) and related types.
+//
+//! - `eyre`: Implements `From<eyre::Report>` for [`PyErr`], so that functions can return
+//! [eyre](https://docs.rs/eyre)'s `Result`.
+//
 //! - `extension-module`: This will tell the linker to keep the Python symbols unresolved,
 //! so that your module can also be used with statically linked Python interpreters.
 //! Use this feature when building an extension module.
 //
+//! - `half`: Implements [`Element`](crate::buffer::Element) for [half](https://docs.rs/half)'s
+//! [`f16`](https://docs.rs/half/latest/half/struct.f16.html), so that buffers of half-precision
+//! floats can be read with [`PyBuffer`](crate::buffer::PyBuffer).
+//
 //! - `hashbrown`: Enables conversions between Python objects and
 //! [hashbrown](https://docs.rs/hashbrown)'s
 //! [`HashMap`](https://docs.rs/hashbrown/latest/hashbrown/struct.HashMap.html) and
@@ -83,6 +103,11 @@
 //! [`#[pyclass]`](crate::proc_macro::pyclass). This adds a dependency on the
 //! [`inventory`](https://docs.rs/inventory) crate, which is not supported on all platforms.
 //
+//! - `ndarray`: Enables converting a [`BufferView`](crate::buffer::BufferView) of a
+//! [`PyBuffer`](crate::buffer::PyBuffer) to [ndarray](https://docs.rs/ndarray)'s
+//! [`ArrayView`](https://docs.rs/ndarray/latest/ndarray/type.ArrayView.html) and
+//! [`ArrayViewMut`](https://docs.rs/ndarray/latest/ndarray/type.ArrayViewMut.html).
+//
 //! - [`num-bigint`](./num_bigint/index.html): Enables conversions between Python objects and
 //! [num-bigint](https://docs.rs/num-bigint)'s
 //! [`BigInt`](https://docs.rs/num-bigint/latest/num_bigint/struct.BigInt.html) and
@@ -90,7 +115,12 @@
 //
 //! - [`num-complex`](crate::num_complex): Enables conversions between Python objects and
 //! [num-complex](https://docs.rs/num-complex)'s
-//! [`Complex`](https://docs.rs/num-complex/latest/num_complex/struct.Complex.html) type.
+//! [`Complex`](https://docs.rs/num-complex/latest/num_complex/struct.Complex.html) type, and
+//! implements [`Element`](crate::buffer::Element) for `Complex<f32>` and `Complex<f64>`.
+//
+//! - [`rust_decimal`](crate::rust_decimal): Enables conversions between Python's
+//! `decimal.Decimal` and [rust_decimal](https://docs.rs/rust_decimal)'s
+//! [`Decimal`](https://docs.rs/rust_decimal/latest/rust_decimal/struct.Decimal.html) type.
 //
 //! - `serde`: Allows implementing [serde](https://docs.rs/serde)'s
 //! [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and
@@ -98,6 +128,13 @@
 //! [`Py`]`<T>` for all `T` that implement
 //! [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html) and
 //! [`Deserialize`](https://docs.rs/serde/latest/serde/trait.Deserialize.html).
+//
+//! - [`smallvec`](crate::smallvec): Enables conversions between Python sequences and
+//! [smallvec](https://docs.rs/smallvec)'s
+//! [`SmallVec`](https://docs.rs/smallvec/latest/smallvec/struct.SmallVec.html).
+//
+//! - [`uuid`](crate::uuid): Enables conversions between Python's `uuid.UUID` and
+//! [uuid](https://docs.rs/uuid)'s [`Uuid`](https://docs.rs/uuid/latest/uuid/struct.Uuid.html) type.
 //!
 //! ## Unstable features
 //!
@@ -267,7 +304,9 @@ pub use crate::conversion::{
     AsPyPointer, FromPyObject, FromPyPointer, IntoPy, IntoPyPointer, PyTryFrom, PyTryInto,
     ToBorrowedObject, ToPyObject,
 };
-pub use crate::err::{PyDowncastError, PyErr, PyErrArguments, PyResult};
+pub use crate::err::{
+    ExtractionError, PathSegment, PyDowncastError, PyErr, PyErrArguments, PyResult,
+};
 #[cfg(not(PyPy))]
 #[cfg_attr(docsrs, doc(cfg(not(PyPy))))]
 pub use crate::gil::{prepare_freethreaded_python, with_embedded_python_interpreter};
@@ -339,10 +378,30 @@ pub mod num_complex;
 #[cfg(feature = "indexmap")]
 pub use crate::conversions::indexmap;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+#[cfg(feature = "arrayvec")]
+pub use crate::conversions::arrayvec;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+#[cfg(feature = "smallvec")]
+pub use crate::conversions::smallvec;
+
+#[cfg_attr(docsrs, doc(cfg(all(feature = "chrono", not(Py_LIMITED_API)))))]
+#[cfg(all(feature = "chrono", not(Py_LIMITED_API)))]
+pub use crate::conversions::chrono;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rust_decimal")))]
+#[cfg(feature = "rust_decimal")]
+pub use crate::conversions::rust_decimal;
+
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 #[cfg(feature = "serde")]
 pub mod serde;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+#[cfg(feature = "uuid")]
+pub use crate::conversions::uuid;
+
 /// The proc macros, all of which are part of the prelude.
 ///
 /// Import these with `use pyo3::prelude::*;`
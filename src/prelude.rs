@@ -21,4 +21,7 @@ pub use crate::{FromPyObject, IntoPy, IntoPyPointer, PyTryFrom, PyTryInto, ToPyO
 pub use crate::types::{PyAny, PyModule};
 pub use crate::wrap_pyfunction;
 #[cfg(feature = "macros")]
-pub use {crate::proc_macro::*, pyo3_macros::FromPyObject};
+pub use {
+    crate::proc_macro::*,
+    pyo3_macros::{FromPyObject, IntoPyObject, ToPyObject},
+};
@@ -1,5 +1,5 @@
 //! Includes `PyCell` implementation.
-use crate::exceptions::PyRuntimeError;
+use crate::exceptions::{PyBufferError, PyRuntimeError};
 use crate::pyclass::PyClass;
 use crate::pyclass_init::PyClassInitializer;
 use crate::pyclass_slots::{PyClassDict, PyClassWeakRef};
@@ -15,6 +15,8 @@ use crate::{
 use crate::{ffi, IntoPy, PyErr, PyNativeType, PyObject, PyResult, Python};
 use std::cell::{Cell, UnsafeCell};
 use std::fmt;
+#[cfg(not(Py_LIMITED_API))]
+use std::marker::PhantomData;
 use std::mem::ManuallyDrop;
 use std::ops::{Deref, DerefMut};
 
@@ -29,6 +31,23 @@ pub struct PyCellBase<T> {
 
 unsafe impl<T, U> PyLayout<T> for PyCellBase<U> where U: PySizedLayout<T> {}
 
+/// Base layout of PyCell for native types whose instances have a variable size, like `tuple`.
+///
+/// The items of such objects are stored inline after their header, so the borrow flag and the
+/// contents of the `PyCell` are instead placed after the items, much like CPython places the
+/// `__dict__` of their subclasses. See [`PyCellLayout::layout_offset`].
+#[doc(hidden)]
+#[repr(C)]
+#[cfg(not(Py_LIMITED_API))]
+pub struct PyCellVarBase<T> {
+    ob_base: ffi::PyVarObject,
+    borrow_flag: Cell<BorrowFlag>,
+    _marker: PhantomData<T>,
+}
+
+#[cfg(not(Py_LIMITED_API))]
+unsafe impl<T: PyTypeInfo> PyLayout<T> for PyCellVarBase<T> {}
+
 /// `PyCell` is the container type for [`PyClass`](../pyclass/trait.PyClass.html) values.
 ///
 /// From the Python side, `PyCell<T>` is the concrete layout of `T: PyClass` in the Python heap,
@@ -248,8 +267,10 @@ impl<T: PyClass> PyCell<T> {
     /// ```
     pub fn try_borrow(&self) -> Result<PyRef<'_, T>, PyBorrowError> {
         let flag = self.get_borrow_flag();
-        if flag == BorrowFlag::HAS_MUTABLE_BORROW {
-            Err(PyBorrowError { _private: () })
+        if flag == BorrowFlag::HAS_MUTABLE_BORROW || flag.has_writable_exports() {
+            Err(PyBorrowError {
+                exported: flag.has_writable_exports(),
+            })
         } else {
             self.set_borrow_flag(flag.increment());
             Ok(PyRef { inner: self })
@@ -278,8 +299,11 @@ impl<T: PyClass> PyCell<T> {
     /// });
     /// ```
     pub fn try_borrow_mut(&self) -> Result<PyRefMut<'_, T>, PyBorrowMutError> {
-        if self.get_borrow_flag() != BorrowFlag::UNUSED {
-            Err(PyBorrowMutError { _private: () })
+        let flag = self.get_borrow_flag();
+        if flag != BorrowFlag::UNUSED {
+            Err(PyBorrowMutError {
+                exported: flag.is_exported(),
+            })
         } else {
             self.set_borrow_flag(BorrowFlag::HAS_MUTABLE_BORROW);
             Ok(PyRefMut { inner: self })
@@ -316,10 +340,13 @@ impl<T: PyClass> PyCell<T> {
     /// });
     /// ```
     pub unsafe fn try_borrow_unguarded(&self) -> Result<&T, PyBorrowError> {
-        if self.get_borrow_flag() == BorrowFlag::HAS_MUTABLE_BORROW {
-            Err(PyBorrowError { _private: () })
+        let flag = self.get_borrow_flag();
+        if flag == BorrowFlag::HAS_MUTABLE_BORROW || flag.has_writable_exports() {
+            Err(PyBorrowError {
+                exported: flag.has_writable_exports(),
+            })
         } else {
-            Ok(&*self.contents.value.get())
+            Ok(&*self.contents().value.get())
         }
     }
 
@@ -354,11 +381,68 @@ impl<T: PyClass> PyCell<T> {
         std::mem::swap(&mut *self.borrow_mut(), &mut *other.borrow_mut())
     }
 
-    fn get_ptr(&self) -> *mut T {
-        self.contents.value.get()
+    pub(crate) fn get_ptr(&self) -> *mut T {
+        self.contents().value.get()
+    }
+
+    fn contents(&self) -> &PyCellContents<T> {
+        unsafe {
+            let offset = Self::layout_offset(self.as_ptr());
+            let cell = (self as *const Self as *const u8).offset(offset) as *const Self;
+            &(*cell).contents
+        }
+    }
+
+    /// Gets the size of the instances of `T`, as `tp_basicsize` of its type object.
+    #[cfg_attr(Py_LIMITED_API, allow(unused_variables))]
+    pub(crate) fn basicsize(py: Python) -> PyResult<usize> {
+        if !<Self as PyCellLayout<T>>::IS_VAR_SIZED {
+            return Ok(std::mem::size_of::<Self>());
+        }
+
+        #[cfg(Py_LIMITED_API)]
+        unreachable!("subclassing native types is not possible with the `abi3` feature");
+
+        #[cfg(not(Py_LIMITED_API))]
+        {
+            // `__dict__` and `__weakref__` would need an offset relative to the end of the
+            // object, which is not supported by `tp_weaklistoffset`
+            if !T::Dict::IS_DUMMY || !T::WeakRef::IS_DUMMY {
+                return Err(crate::exceptions::PyTypeError::new_err(format!(
+                    "`dict` and `weakref` are not supported for subclasses of `{}`",
+                    <T::BaseNativeType as PyTypeInfo>::NAME
+                )));
+            }
+            if std::mem::align_of::<Self>() > ALIGNMENT {
+                return Err(crate::exceptions::PyTypeError::new_err(format!(
+                    "the alignment of `{}` is too large for a subclass of `{}`",
+                    T::NAME,
+                    <T::BaseNativeType as PyTypeInfo>::NAME
+                )));
+            }
+            let native_size =
+                unsafe { (*T::BaseNativeType::type_object_raw(py)).tp_basicsize } as usize;
+            let trailing_size =
+                std::mem::size_of::<Self>() - std::mem::size_of::<ffi::PyVarObject>();
+            // leave room for aligning the end of the items, see `PyCellVarBase::layout_offset`
+            Ok(round_up(
+                native_size + ALIGNMENT - 1 + trailing_size,
+                ALIGNMENT,
+            ))
+        }
     }
 }
 
+/// The alignment of the PyCell of subclasses of variable-size types, which is the alignment
+/// CPython uses when computing the size of such objects.
+#[cfg(not(Py_LIMITED_API))]
+const ALIGNMENT: usize = std::mem::size_of::<*mut ffi::PyObject>();
+
+#[cfg(not(Py_LIMITED_API))]
+fn round_up(value: usize, align: usize) -> usize {
+    (value + align - 1) & !(align - 1)
+}
+
 unsafe impl<T: PyClass> PyLayout<T> for PyCell<T> {}
 impl<T: PyClass> PySizedLayout<T> for PyCell<T> {}
 
@@ -667,22 +751,56 @@ impl<T: PyClass + fmt::Debug> fmt::Debug for PyRefMut<'_, T> {
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub struct BorrowFlag(usize);
 
+// Buffers exported with `BufferExport` are tracked in the borrow flag as well: a read-only export
+// counts as `READONLY_EXPORT` shared borrows, and writable exports, which behave like a mutable
+// borrow, count up from `WRITABLE_EXPORTS`.
 impl BorrowFlag {
     pub(crate) const UNUSED: BorrowFlag = BorrowFlag(0);
-    const HAS_MUTABLE_BORROW: BorrowFlag = BorrowFlag(usize::max_value());
+    pub(crate) const HAS_MUTABLE_BORROW: BorrowFlag = BorrowFlag(usize::max_value());
+    const READONLY_EXPORT: usize = 1 << (std::mem::size_of::<usize>() * 4);
+    const WRITABLE_EXPORTS: usize = 1 << (std::mem::size_of::<usize>() * 8 - 1);
     const fn increment(self) -> Self {
         Self(self.0 + 1)
     }
     const fn decrement(self) -> Self {
         Self(self.0 - 1)
     }
+
+    /// Whether a buffer of the value is currently exported.
+    pub(crate) fn is_exported(self) -> bool {
+        self.0 >= Self::READONLY_EXPORT && self != Self::HAS_MUTABLE_BORROW
+    }
+
+    /// Whether a writable buffer of the value is currently exported.
+    pub(crate) fn has_writable_exports(self) -> bool {
+        self.0 > Self::WRITABLE_EXPORTS && self != Self::HAS_MUTABLE_BORROW
+    }
+
+    pub(crate) fn add_export(self, writable: bool) -> Self {
+        match (writable, self.has_writable_exports()) {
+            (true, true) => Self(self.0 + 1),
+            (true, false) => Self(Self::WRITABLE_EXPORTS + 1),
+            (false, _) => Self(self.0 + Self::READONLY_EXPORT),
+        }
+    }
+
+    pub(crate) fn remove_export(self, writable: bool) -> Self {
+        if !writable {
+            Self(self.0 - Self::READONLY_EXPORT)
+        } else if self.0 == Self::WRITABLE_EXPORTS + 1 {
+            Self::UNUSED
+        } else {
+            Self(self.0 - 1)
+        }
+    }
 }
 
 /// An error returned by [`PyCell::try_borrow`](struct.PyCell.html#method.try_borrow).
 ///
-/// In Python, you can catch this error using `except RuntimeError`.
+/// In Python, you can catch this error using `except RuntimeError`, or `except BufferError` if
+/// the value is exported as a writable buffer.
 pub struct PyBorrowError {
-    _private: (),
+    exported: bool,
 }
 
 impl fmt::Debug for PyBorrowError {
@@ -693,21 +811,30 @@ impl fmt::Debug for PyBorrowError {
 
 impl fmt::Display for PyBorrowError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt("Already mutably borrowed", f)
+        if self.exported {
+            fmt::Display::fmt("Cannot borrow while a writable buffer is exported", f)
+        } else {
+            fmt::Display::fmt("Already mutably borrowed", f)
+        }
     }
 }
 
 impl From<PyBorrowError> for PyErr {
     fn from(other: PyBorrowError) -> Self {
-        PyRuntimeError::new_err(other.to_string())
+        if other.exported {
+            PyBufferError::new_err(other.to_string())
+        } else {
+            PyRuntimeError::new_err(other.to_string())
+        }
     }
 }
 
 /// An error returned by [`PyCell::try_borrow_mut`](struct.PyCell.html#method.try_borrow_mut).
 ///
-/// In Python, you can catch this error using `except RuntimeError`.
+/// In Python, you can catch this error using `except RuntimeError`, or `except BufferError` if
+/// the value is exported as a buffer.
 pub struct PyBorrowMutError {
-    _private: (),
+    exported: bool,
 }
 
 impl fmt::Debug for PyBorrowMutError {
@@ -718,20 +845,37 @@ impl fmt::Debug for PyBorrowMutError {
 
 impl fmt::Display for PyBorrowMutError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt("Already borrowed", f)
+        if self.exported {
+            fmt::Display::fmt("Cannot mutably borrow while a buffer is exported", f)
+        } else {
+            fmt::Display::fmt("Already borrowed", f)
+        }
     }
 }
 
 impl From<PyBorrowMutError> for PyErr {
     fn from(other: PyBorrowMutError) -> Self {
-        PyRuntimeError::new_err(other.to_string())
+        if other.exported {
+            PyBufferError::new_err(other.to_string())
+        } else {
+            PyRuntimeError::new_err(other.to_string())
+        }
     }
 }
 
 #[doc(hidden)]
 pub trait PyCellLayout<T>: PyLayout<T> {
+    /// Whether the instances of the closest native base type have a variable size.
+    const IS_VAR_SIZED: bool = false;
     fn get_borrow_flag(&self) -> BorrowFlag;
     fn set_borrow_flag(&self, flag: BorrowFlag);
+    /// Offset from the start of the object to the start of this layout, which is only nonzero
+    /// for subclasses of variable-size native types.
+    /// # Safety
+    /// - slf must be a valid pointer to an instance of a T or a subclass.
+    unsafe fn layout_offset(_slf: *mut ffi::PyObject) -> isize {
+        0
+    }
     /// Implementation of tp_dealloc.
     /// # Safety
     /// - slf must be a valid pointer to an instance of a T or a subclass.
@@ -771,20 +915,59 @@ where
     }
 }
 
+#[cfg(not(Py_LIMITED_API))]
+impl<T: PyTypeInfo> PyCellLayout<T> for PyCellVarBase<T> {
+    const IS_VAR_SIZED: bool = true;
+    fn get_borrow_flag(&self) -> BorrowFlag {
+        unsafe { (*self.shifted()).borrow_flag.get() }
+    }
+    fn set_borrow_flag(&self, flag: BorrowFlag) {
+        unsafe { (*self.shifted()).borrow_flag.set(flag) }
+    }
+    unsafe fn layout_offset(slf: *mut ffi::PyObject) -> isize {
+        // The items end at the same place for all subclasses, which makes the offset the same
+        // for every `PyCell` in the hierarchy.
+        let type_object = T::type_object_raw(Python::assume_gil_acquired());
+        let items_end =
+            (*type_object).tp_basicsize + ffi::Py_SIZE(slf).abs() * (*type_object).tp_itemsize;
+        let header_size = std::mem::size_of::<ffi::PyVarObject>();
+        (round_up(items_end as usize, ALIGNMENT) - header_size) as isize
+    }
+    unsafe fn tp_dealloc(slf: *mut ffi::PyObject, py: Python) {
+        if let Some(dealloc) = (*T::type_object_raw(py)).tp_dealloc {
+            dealloc(slf as _);
+        } else {
+            get_tp_free(ffi::Py_TYPE(slf))(slf as _);
+        }
+    }
+}
+
+#[cfg(not(Py_LIMITED_API))]
+impl<T: PyTypeInfo> PyCellVarBase<T> {
+    fn shifted(&self) -> *const Self {
+        let slf = self as *const Self;
+        unsafe { (slf as *const u8).offset(Self::layout_offset(slf as _)) as *const Self }
+    }
+}
+
 impl<T: PyClass> PyCellLayout<T> for PyCell<T>
 where
     <T::BaseType as PyClassBaseType>::LayoutAsBase: PyCellLayout<T::BaseType>,
 {
+    const IS_VAR_SIZED: bool = <T::BaseType as PyClassBaseType>::LayoutAsBase::IS_VAR_SIZED;
     fn get_borrow_flag(&self) -> BorrowFlag {
-        self.contents.thread_checker.ensure();
+        self.contents().thread_checker.ensure();
         self.ob_base.get_borrow_flag()
     }
     fn set_borrow_flag(&self, flag: BorrowFlag) {
         self.ob_base.set_borrow_flag(flag)
     }
+    unsafe fn layout_offset(slf: *mut ffi::PyObject) -> isize {
+        <T::BaseType as PyClassBaseType>::LayoutAsBase::layout_offset(slf)
+    }
     unsafe fn tp_dealloc(slf: *mut ffi::PyObject, py: Python) {
         // Safety: Python only calls tp_dealloc when no references to the object remain.
-        let cell = &mut *(slf as *mut PyCell<T>);
+        let cell = &mut *((slf as *mut u8).offset(Self::layout_offset(slf)) as *mut PyCell<T>);
         ManuallyDrop::drop(&mut cell.contents.value);
         cell.contents.dict.clear_dict(py);
         cell.contents.weakref.clear_weakrefs(slf, py);
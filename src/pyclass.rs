@@ -3,6 +3,7 @@ use crate::{
     class::impl_::{fallback_new, tp_dealloc, PyClassImpl},
     ffi,
     pyclass_slots::{PyClassDict, PyClassWeakRef},
+    types::PyType,
     PyCell, PyErr, PyMethodDefType, PyNativeType, PyResult, PyTypeInfo, Python,
 };
 use std::{
@@ -29,6 +30,38 @@ pub trait PyClass:
     type BaseNativeType: PyTypeInfo + PyNativeType;
 }
 
+/// Types which can be used as the metaclass of a `#[pyclass]`, with
+/// `#[pyclass(metaclass = ...)]`.
+///
+/// This is implemented for [`PyType`](crate::types::PyType), which is the default metaclass,
+/// and for every `#[pyclass]` which extends `PyType` (directly or through another `#[pyclass]`)
+/// and implements `Default`.
+///
+/// # Safety
+///
+/// `init_type_object` must fully initialize the Rust data of the metaclass, if it has any.
+pub unsafe trait PyMetaclass: PyTypeInfo {
+    /// Initializes the Rust data stored in `type_object`, which is a type object created by
+    /// PyO3 as an instance of this metaclass.
+    #[doc(hidden)]
+    unsafe fn init_type_object(py: Python, type_object: *mut ffi::PyObject);
+}
+
+unsafe impl PyMetaclass for PyType {
+    unsafe fn init_type_object(_py: Python, _type_object: *mut ffi::PyObject) {}
+}
+
+unsafe impl<T> PyMetaclass for T
+where
+    T: PyClass + Default,
+    T::BaseType: PyMetaclass,
+{
+    unsafe fn init_type_object(py: Python, type_object: *mut ffi::PyObject) {
+        T::BaseType::init_type_object(py, type_object);
+        crate::pyclass_init::write_contents(type_object, T::default());
+    }
+}
+
 /// For collecting slot items.
 #[derive(Default)]
 struct TypeSlots(Vec<ffi::PyType_Slot>);
@@ -66,6 +99,7 @@ pub(crate) fn create_type_object<T>(
 where
     T: PyClass,
 {
+    check_native_layout::<T>(py)?;
     let mut slots = TypeSlots::default();
 
     slots.push(ffi::Py_tp_base, T::BaseType::type_object_raw(py) as _);
@@ -115,22 +149,128 @@ where
         slots.0.extend_from_slice(proto_slots);
     });
 
+    // the class object of a class with a metaclass derives from the type object created here
+    let has_metaclass = T::Metaclass::type_object_raw(py) != PyType::type_object_raw(py);
     slots.push(0, ptr::null_mut());
     let mut spec = ffi::PyType_Spec {
         name: get_type_name::<T>(module_name)?,
-        basicsize: std::mem::size_of::<T::Layout>() as c_int,
+        basicsize: PyCell::<T>::basicsize(py)? as c_int,
         itemsize: 0,
-        flags: py_class_flags(has_gc_methods, T::IS_GC, T::IS_BASETYPE),
+        flags: py_class_flags(has_gc_methods, T::IS_GC, T::IS_BASETYPE || has_metaclass),
         slots: slots.0.as_mut_ptr(),
     };
 
     let type_object = unsafe { ffi::PyType_FromSpec(&mut spec) };
     if type_object.is_null() {
-        Err(PyErr::api_call_failed(py))
-    } else {
-        tp_init_additional::<T>(type_object as _);
-        Ok(type_object as _)
+        return Err(PyErr::api_call_failed(py));
     }
+    let type_object = apply_metaclass::<T>(py, type_object as _)?;
+    tp_init_additional::<T>(type_object);
+    Ok(type_object)
+}
+
+/// The Rust data of a metaclass is stored after the `PyHeapTypeObject` declared in `ffi`, which
+/// has to match the layout of the running Python version.
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+fn check_native_layout<T: PyClass>(py: Python) -> PyResult<()> {
+    let type_type = PyType::type_object_raw(py);
+    if T::BaseNativeType::type_object_raw(py) == type_type
+        && unsafe { (*type_type).tp_basicsize } as usize
+            != std::mem::size_of::<ffi::PyHeapTypeObject>()
+    {
+        return Err(crate::exceptions::PyTypeError::new_err(
+            "extending `type` is not supported on this version of Python",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(any(Py_LIMITED_API, PyPy))]
+fn check_native_layout<T: PyClass>(_py: Python) -> PyResult<()> {
+    Ok(())
+}
+
+/// Makes the type object an instance of `T::Metaclass`, if that isn't `type`.
+fn apply_metaclass<T: PyClass>(
+    py: Python,
+    type_object: *mut ffi::PyTypeObject,
+) -> PyResult<*mut ffi::PyTypeObject> {
+    let metaclass = T::Metaclass::type_object_raw(py);
+    unsafe {
+        let base_metaclass = ffi::Py_TYPE(T::BaseType::type_object_raw(py) as _);
+        if ffi::PyType_IsSubtype(metaclass, base_metaclass) == 0 {
+            ffi::Py_DECREF(type_object as _);
+            return Err(crate::exceptions::PyTypeError::new_err(
+                "metaclass conflict: the metaclass of a derived class must be a \
+                 (non-strict) subclass of the metaclasses of all its bases",
+            ));
+        }
+        if metaclass == PyType::type_object_raw(py) {
+            return Ok(type_object);
+        }
+        create_with_metaclass(
+            py,
+            type_object,
+            T::IS_BASETYPE,
+            metaclass,
+            T::Metaclass::init_type_object,
+        )
+    }
+}
+
+/// `PyType_FromSpec` always creates instances of `type`, and `PyType_FromMetaclass` (Python
+/// 3.12+) rejects metaclasses with their own `tp_new`, which every `#[pyclass]` metaclass has.
+/// So the class object is created like a class statement does, by calling the `tp_new` of `type`
+/// with the metaclass and `(name, (type_object,), namespace)`, which is what the `#[new]` of a
+/// metaclass does as well: the class inherits the slots of the type object created from the
+/// spec and adds none of its own, so its instances have the same layout. The Rust data of the
+/// metaclass is initialized afterwards.
+///
+/// # Safety
+/// `type_object` must be an owned reference to a type object just created by `PyType_FromSpec`,
+/// and `metaclass` must be a subclass of `type`.
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+unsafe fn create_with_metaclass(
+    py: Python,
+    type_object: *mut ffi::PyTypeObject,
+    is_basetype: bool,
+    metaclass: *mut ffi::PyTypeObject,
+    init_type_object: unsafe fn(Python, *mut ffi::PyObject),
+) -> PyResult<*mut ffi::PyTypeObject> {
+    use crate::types::{PyDict, PyTuple};
+    use crate::{AsPyPointer, IntoPy, PyObject};
+
+    let base: &PyType = py.from_owned_ptr(type_object as _);
+    let namespace = PyDict::new(py);
+    for &key in &["__module__", "__qualname__", "__doc__"] {
+        namespace.set_item(key, base.getattr(key)?)?;
+    }
+    // no `__dict__` or `__weakref__` beyond those of the base
+    namespace.set_item("__slots__", PyTuple::empty(py))?;
+    let args: PyObject = (base.getattr("__name__")?, (base,), namespace).into_py(py);
+    let type_new = ffi::PyType_Type.tp_new.expect("type has tp_new");
+    let class = type_new(metaclass, args.as_ptr(), ptr::null_mut());
+    if class.is_null() {
+        return Err(PyErr::api_call_failed(py));
+    }
+
+    init_type_object(py, class);
+    let ty = class as *mut ffi::PyTypeObject;
+    if !is_basetype {
+        (*ty).tp_flags &= !ffi::Py_TPFLAGS_BASETYPE;
+    }
+    Ok(ty)
+}
+
+#[cfg(any(Py_LIMITED_API, PyPy))]
+unsafe fn create_with_metaclass(
+    _py: Python,
+    _type_object: *mut ffi::PyTypeObject,
+    _is_basetype: bool,
+    _metaclass: *mut ffi::PyTypeObject,
+    _init_type_object: unsafe fn(Python, *mut ffi::PyObject),
+) -> PyResult<*mut ffi::PyTypeObject> {
+    unreachable!("subclassing `type` is not possible with the `abi3` feature or on PyPy");
 }
 
 /// Additional type initializations necessary before Python 3.10
@@ -1,15 +1,18 @@
 //! Initialization utilities for `#[pyclass]`.
 use crate::class::impl_::PyClassThreadChecker;
 use crate::pyclass_slots::{PyClassDict, PyClassWeakRef};
+use crate::types::PyTuple;
+#[cfg(not(Py_LIMITED_API))]
+use crate::AsPyPointer;
 use crate::{callback::IntoPyCallbackOutput, class::impl_::PyClassBaseType};
-use crate::{ffi, PyCell, PyClass, PyErr, PyResult, Python};
+use crate::{ffi, IntoPy, Py, PyCell, PyClass, PyErr, PyResult, Python};
 use crate::{
     ffi::PyTypeObject,
-    pycell::{BorrowFlag, PyCellContents},
+    pycell::{BorrowFlag, PyCellContents, PyCellLayout},
     type_object::{get_tp_alloc, PyTypeInfo},
 };
 use std::{
-    cell::{Cell, UnsafeCell},
+    cell::UnsafeCell,
     marker::PhantomData,
     mem::{ManuallyDrop, MaybeUninit},
 };
@@ -30,7 +33,10 @@ pub trait PyObjectInit<T>: Sized {
 }
 
 /// Initializer for Python native types, like `PyDict`.
-pub struct PyNativeTypeInitializer<T: PyTypeInfo>(PhantomData<T>);
+pub struct PyNativeTypeInitializer<T: PyTypeInfo> {
+    args: Option<Py<PyTuple>>,
+    _marker: PhantomData<T>,
+}
 
 impl<T: PyTypeInfo> PyObjectInit<T> for PyNativeTypeInitializer<T> {
     unsafe fn into_new_object(
@@ -42,6 +48,13 @@ impl<T: PyTypeInfo> PyObjectInit<T> for PyNativeTypeInitializer<T> {
 
         // HACK (due to FIXME below): PyBaseObject_Type's tp_new isn't happy with NULL arguments
         if type_object == (&ffi::PyBaseObject_Type as *const _ as *mut _) {
+            if let Some(args) = &self.args {
+                if !args.as_ref(py).is_empty() {
+                    return Err(crate::exceptions::PyTypeError::new_err(
+                        "object.__new__() takes no arguments",
+                    ));
+                }
+            }
             let alloc = get_tp_alloc(subtype).unwrap_or(ffi::PyType_GenericAlloc);
             let obj = alloc(subtype, 0);
             return if obj.is_null() {
@@ -57,9 +70,15 @@ impl<T: PyTypeInfo> PyObjectInit<T> for PyNativeTypeInitializer<T> {
         #[cfg(not(Py_LIMITED_API))]
         {
             match (*type_object).tp_new {
-                // FIXME: Call __new__ with actual arguments
+                // FIXME: Call __new__ with the actual arguments of the Python call; only those set
+                // with `PyClassInitializer::with_base_args` are passed for now
                 Some(newfunc) => {
-                    let obj = newfunc(subtype, std::ptr::null_mut(), std::ptr::null_mut());
+                    // many `__new__` implementations, like that of `tuple`, require an args tuple
+                    let args = match &self.args {
+                        Some(args) => args.as_ref(py),
+                        None => PyTuple::empty(py),
+                    };
+                    let obj = newfunc(subtype, args.as_ptr(), std::ptr::null_mut());
                     if obj.is_null() {
                         Err(PyErr::api_call_failed(py))
                     } else {
@@ -201,55 +220,85 @@ impl<T: PyClass> PyClassInitializer<T> {
     }
 }
 
+impl<T> PyClassInitializer<T>
+where
+    T: PyClass,
+    T::BaseType: PyClassBaseType<Initializer = PyNativeTypeInitializer<T::BaseType>>,
+{
+    /// Sets the arguments passed to `__new__` of the native base type when the object is created.
+    ///
+    /// By default `__new__` of the base type is called without arguments. Some native types
+    /// store their arguments: for example exceptions keep them as `args`, which is used by
+    /// the default `__str__` and for pickling.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[cfg(not(Py_LIMITED_API))] {
+    /// # use pyo3::prelude::*;
+    /// use pyo3::exceptions::PyException;
+    ///
+    /// #[pyclass(extends=PyException)]
+    /// struct ParseError {
+    ///     line: usize,
+    /// }
+    ///
+    /// Python::with_gil(|py| {
+    ///     let init = PyClassInitializer::from(ParseError { line: 3 })
+    ///         .with_base_args(py, ("unexpected token",));
+    ///     let err = PyErr::from(init);
+    ///     assert_eq!(err.to_string(), "ParseError: unexpected token");
+    /// });
+    /// # }
+    /// ```
+    pub fn with_base_args(mut self, py: Python, args: impl IntoPy<Py<PyTuple>>) -> Self {
+        self.super_init.args = Some(args.into_py(py));
+        self
+    }
+}
+
 impl<T: PyClass> PyObjectInit<T> for PyClassInitializer<T> {
     unsafe fn into_new_object(
         self,
         py: Python,
         subtype: *mut PyTypeObject,
     ) -> PyResult<*mut ffi::PyObject> {
-        /// Layout of a PyCellBase after base new has been called, but the borrow flag has not
-        /// yet been initialized.
-        #[repr(C)]
-        struct PartiallyInitializedPyCellBase<T> {
-            _ob_base: T,
-            borrow_flag: MaybeUninit<Cell<BorrowFlag>>,
-        }
-
-        /// Layout of a PyCell after base new has been called, but the contents have not yet been
-        /// written.
-        #[repr(C)]
-        struct PartiallyInitializedPyCell<T: PyClass> {
-            _ob_base: <T::BaseType as PyClassBaseType>::LayoutAsBase,
-            contents: MaybeUninit<PyCellContents<T>>,
-        }
-
         let Self { init, super_init } = self;
         let obj = super_init.into_new_object(py, subtype)?;
-
-        // FIXME: Only need to initialize borrow flag once per whole hierarchy
-        let base: *mut PartiallyInitializedPyCellBase<T::BaseNativeType> = obj as _;
-        std::ptr::write(
-            (*base).borrow_flag.as_mut_ptr(),
-            Cell::new(BorrowFlag::UNUSED),
-        );
-
-        // FIXME: Initialize borrow flag if necessary??
-        let cell: *mut PartiallyInitializedPyCell<T> = obj as _;
-        std::ptr::write(
-            (*cell).contents.as_mut_ptr(),
-            PyCellContents {
-                value: ManuallyDrop::new(UnsafeCell::new(init)),
-                thread_checker: T::ThreadChecker::new(),
-                dict: T::Dict::new(),
-                weakref: T::WeakRef::new(),
-            },
-        );
+        write_contents(obj, init);
         Ok(obj)
     }
 
     private_impl! {}
 }
 
+/// Writes the contents of the `PyCell<T>` in `obj`, whose base class parts are already
+/// initialized.
+pub(crate) unsafe fn write_contents<T: PyClass>(obj: *mut ffi::PyObject, init: T) {
+    /// Layout of a PyCell after base new has been called, but the contents have not yet been
+    /// written.
+    #[repr(C)]
+    struct PartiallyInitializedPyCell<T: PyClass> {
+        _ob_base: <T::BaseType as PyClassBaseType>::LayoutAsBase,
+        contents: MaybeUninit<PyCellContents<T>>,
+    }
+
+    // For subclasses of variable-size types the PyCell is stored after the items
+    let offset = <PyCell<T> as PyCellLayout<T>>::layout_offset(obj);
+    let cell = (obj as *mut u8).offset(offset) as *mut PartiallyInitializedPyCell<T>;
+    std::ptr::write(
+        (*cell).contents.as_mut_ptr(),
+        PyCellContents {
+            value: ManuallyDrop::new(UnsafeCell::new(init)),
+            thread_checker: T::ThreadChecker::new(),
+            dict: T::Dict::new(),
+            weakref: T::WeakRef::new(),
+        },
+    );
+
+    // FIXME: Only need to initialize borrow flag once per whole hierarchy
+    (*(obj as *const PyCell<T>)).set_borrow_flag(BorrowFlag::UNUSED);
+}
+
 impl<T> From<T> for PyClassInitializer<T>
 where
     T: PyClass,
@@ -257,7 +306,13 @@ where
 {
     #[inline]
     fn from(value: T) -> PyClassInitializer<T> {
-        Self::new(value, PyNativeTypeInitializer(PhantomData))
+        Self::new(
+            value,
+            PyNativeTypeInitializer {
+                args: None,
+                _marker: PhantomData,
+            },
+        )
     }
 }
 
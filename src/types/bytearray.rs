@@ -2,14 +2,16 @@
 use crate::err::{PyErr, PyResult};
 use crate::instance::PyNativeType;
 use crate::{ffi, AsPyPointer, Py, PyAny, Python};
+#[cfg(not(Py_LIMITED_API))]
+use std::cell::Cell;
 use std::os::raw::c_char;
-use std::slice;
+use std::{io, ptr, slice};
 
 /// Represents a Python `bytearray`.
 #[repr(transparent)]
 pub struct PyByteArray(PyAny);
 
-pyobject_native_type_core!(PyByteArray, ffi::PyByteArray_Type, #checkfunction=ffi::PyByteArray_Check);
+pyobject_native_type!(PyByteArray, ffi::PyByteArrayObject, ffi::PyByteArray_Type, #checkfunction=ffi::PyByteArray_Check);
 
 impl PyByteArray {
     /// Creates a new Python bytearray object.
@@ -118,6 +120,8 @@ impl PyByteArray {
 
     /// Get the contents of this buffer as a mutable slice.
     ///
+    /// See [PyByteArray::with_bytes_mut] for a safe alternative.
+    ///
     /// # Safety
     /// This slice should only be used for short-lived operations that write to this bytearray
     /// without executing any Python code. See the safety note for [PyByteArray::as_bytes].
@@ -126,6 +130,45 @@ impl PyByteArray {
         slice::from_raw_parts_mut(self.data(), self.len())
     }
 
+    /// Calls `f` with the contents of the bytearray as a slice of [`Cell`]s.
+    ///
+    /// For the duration of the call, the bytearray is exported like through a `memoryview`, so
+    /// attempts to resize it, from Python code as well as through [PyByteArray::resize], fail
+    /// with `BufferError` rather than invalidating the slice.
+    ///
+    /// Python code which runs during the call, such as callbacks called by `f`, can still change
+    /// the contents of the bytearray, which is why they are given as `Cell`s rather than as a
+    /// `&mut [u8]`. As `Cell`s are not `Sync`, the slice cannot be used while the GIL is released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use pyo3::prelude::*;
+    /// # use pyo3::types::PyByteArray;
+    /// # Python::with_gil(|py| -> PyResult<()> {
+    /// let bytearray = PyByteArray::new(py, b"hello");
+    /// bytearray.with_bytes_mut(|bytes| {
+    ///     for byte in bytes {
+    ///         byte.set(byte.get().to_ascii_uppercase());
+    ///     }
+    /// })?;
+    /// assert_eq!(bytearray.to_vec(), b"HELLO");
+    /// # Ok(())
+    /// # }).unwrap();
+    /// ```
+    #[cfg(not(Py_LIMITED_API))]
+    pub fn with_bytes_mut<F, R>(&self, f: F) -> PyResult<R>
+    where
+        F: FnOnce(&[Cell<u8>]) -> R,
+    {
+        let export = crate::buffer::PyBuffer::<u8>::get(self)?;
+        // `Cell<u8>` has the same layout as `u8`; no `&mut` is created, as `f` may export the
+        // bytearray again while the slice is alive
+        let bytes =
+            unsafe { slice::from_raw_parts(export.buf_ptr() as *const Cell<u8>, self.len()) };
+        Ok(f(bytes))
+    }
+
     /// Copies the contents of the bytearray to a Rust vector.
     ///
     /// # Examples
@@ -164,11 +207,29 @@ impl PyByteArray {
     }
 }
 
+/// Appends to the end of the bytearray, growing it as needed.
+///
+/// On failure, the returned `io::Error` wraps the Python exception, and converting it into a
+/// [PyErr] gives back that exception.
+impl io::Write for &PyByteArray {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.len();
+        self.resize(len + buf.len())
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        unsafe { ptr::copy_nonoverlapping(buf.as_ptr(), self.data().add(len), buf.len()) };
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::exceptions;
     use crate::types::PyByteArray;
-    use crate::{PyObject, Python};
+    use crate::{PyErr, PyObject, Python};
 
     #[test]
     fn test_len() {
@@ -293,4 +354,61 @@ mod tests {
                 .is_instance::<PyValueError>(py));
         })
     }
+
+    #[test]
+    fn test_write() {
+        use std::io::Write;
+        Python::with_gil(|py| {
+            let bytearray = PyByteArray::new(py, b"head:");
+            let mut writer = bytearray;
+            for i in 0..3 {
+                write!(writer, "{},", i).unwrap();
+            }
+            assert_eq!(bytearray.to_vec(), b"head:0,1,2,");
+        })
+    }
+
+    #[cfg(not(Py_LIMITED_API))]
+    #[test]
+    fn test_with_bytes_mut() {
+        use crate::types::IntoPyDict;
+        use std::io::Write;
+        Python::with_gil(|py| {
+            let bytearray = PyByteArray::new(py, b"abc");
+            let locals = [("bytearray", bytearray)].into_py_dict(py);
+            let len = bytearray
+                .with_bytes_mut(|bytes| {
+                    bytes[0].set(b'x');
+
+                    // the bytearray cannot be resized while the slice exists
+                    let err = py
+                        .run("bytearray.append(1)", None, Some(locals))
+                        .unwrap_err();
+                    assert!(err.is_instance::<exceptions::PyBufferError>(py));
+                    assert!(bytearray.resize(10).is_err());
+                    let mut writer = bytearray;
+                    let err: PyErr = writer.write_all(b"d").unwrap_err().into();
+                    assert!(err.is_instance::<exceptions::PyBufferError>(py));
+
+                    // changes made by Python code are visible through the slice
+                    py.run("bytearray[1] = ord('y')", None, Some(locals))
+                        .unwrap();
+                    assert_eq!(bytes[1].get(), b'y');
+                    bytearray
+                        .with_bytes_mut(|inner| inner[2].set(b'z'))
+                        .unwrap();
+                    assert_eq!(bytes[2].get(), b'z');
+                    bytes.len()
+                })
+                .unwrap();
+            assert_eq!(len, 3);
+            assert_eq!(bytearray.to_vec(), b"xyz");
+
+            bytearray.resize(4).unwrap();
+            bytearray
+                .with_bytes_mut(|bytes| bytes[3].set(b'!'))
+                .unwrap();
+            assert_eq!(bytearray.to_vec(), b"xyz!");
+        })
+    }
 }
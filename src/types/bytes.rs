@@ -1,3 +1,5 @@
+#[cfg(not(Py_LIMITED_API))]
+use crate::{exceptions::PyMemoryError, PyErr};
 use crate::{
     ffi, AsPyPointer, FromPyObject, IntoPy, Py, PyAny, PyObject, PyResult, PyTryFrom, Python,
     ToPyObject,
@@ -6,6 +8,8 @@ use std::ops::Index;
 use std::os::raw::c_char;
 use std::slice::SliceIndex;
 use std::str;
+#[cfg(not(Py_LIMITED_API))]
+use std::{io, ptr};
 
 /// Represents a Python `bytes` object.
 ///
@@ -13,7 +17,7 @@ use std::str;
 #[repr(transparent)]
 pub struct PyBytes(PyAny);
 
-pyobject_native_type_core!(PyBytes, ffi::PyBytes_Type, #checkfunction=ffi::PyBytes_Check);
+pyobject_native_var_type!(PyBytes, ffi::PyBytes_Type, #checkfunction=ffi::PyBytes_Check);
 
 impl PyBytes {
     /// Creates a new Python bytestring object.
@@ -102,6 +106,135 @@ impl<I: SliceIndex<[u8]>> Index<I> for PyBytes {
     }
 }
 
+/// Writes a new Python `bytes` object incrementally, through [`std::io::Write`].
+///
+/// The data is written directly into the `bytes` object, which grows as needed and is shrunk to
+/// the written length by [`finish`](PyBytesWriter::finish), so that no copy of the whole
+/// contents is made. To append to a `bytearray` instead, use its implementation of `Write`.
+///
+/// # Examples
+/// ```
+/// use pyo3::{prelude::*, types::PyBytesWriter};
+/// use std::io::Write;
+///
+/// Python::with_gil(|py| -> PyResult<()> {
+///     let mut writer = PyBytesWriter::new(py)?;
+///     for i in 0..3 {
+///         write!(writer, "line {}\n", i)?;
+///     }
+///     let bytes = writer.finish()?;
+///     assert_eq!(bytes.as_bytes(), b"line 0\nline 1\nline 2\n");
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[cfg(not(Py_LIMITED_API))]
+pub struct PyBytesWriter<'py> {
+    py: Python<'py>,
+    // A `bytes` object which is not shared with any other code, so that it can be resized. Its
+    // length is the capacity of the writer, of which the first `len` bytes have been written.
+    bytes: *mut ffi::PyObject,
+    len: usize,
+}
+
+#[cfg(not(Py_LIMITED_API))]
+impl<'py> PyBytesWriter<'py> {
+    /// Creates a new writer.
+    pub fn new(py: Python<'py>) -> PyResult<Self> {
+        Self::with_capacity(py, 0)
+    }
+
+    /// Creates a new writer, which can hold at least `capacity` bytes before it needs to grow.
+    pub fn with_capacity(py: Python<'py>, capacity: usize) -> PyResult<Self> {
+        // `bytes` objects of length zero are shared, and therefore cannot be resized
+        let capacity = capacity.max(1) as ffi::Py_ssize_t;
+        let bytes = unsafe { ffi::PyBytes_FromStringAndSize(ptr::null(), capacity) };
+        if bytes.is_null() {
+            return Err(PyErr::api_call_failed(py));
+        }
+        Ok(PyBytesWriter { py, bytes, len: 0 })
+    }
+
+    /// Gets the number of bytes written.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if no bytes have been written.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets the number of bytes the writer can hold before it needs to grow.
+    pub fn capacity(&self) -> usize {
+        if self.bytes.is_null() {
+            0
+        } else {
+            unsafe { ffi::PyBytes_Size(self.bytes) as usize }
+        }
+    }
+
+    /// Makes sure that at least `additional` more bytes can be written without growing.
+    pub fn reserve(&mut self, additional: usize) -> PyResult<()> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or_else(|| PyMemoryError::new_err("capacity overflow"))?;
+        let capacity = self.capacity();
+        if required > capacity {
+            self.resize(required.max(capacity.saturating_mul(2)))?;
+        }
+        Ok(())
+    }
+
+    /// Shrinks the `bytes` object to the written data and returns it.
+    pub fn finish(mut self) -> PyResult<&'py PyBytes> {
+        self.resize(self.len)?;
+        let bytes = std::mem::replace(&mut self.bytes, ptr::null_mut());
+        unsafe { self.py.from_owned_ptr_or_err(bytes) }
+    }
+
+    fn resize(&mut self, size: usize) -> PyResult<()> {
+        if self.bytes.is_null() {
+            return Err(PyMemoryError::new_err(
+                "cannot use a PyBytesWriter after it failed to grow",
+            ));
+        }
+        // on failure, `_PyBytes_Resize` deallocates the object and sets the pointer to null
+        if unsafe { ffi::_PyBytes_Resize(&mut self.bytes, size as ffi::Py_ssize_t) } != 0 {
+            return Err(PyErr::api_call_failed(self.py));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(Py_LIMITED_API))]
+impl io::Write for PyBytesWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.reserve(buf.len())
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        unsafe {
+            let data = ffi::PyBytes_AsString(self.bytes) as *mut u8;
+            ptr::copy_nonoverlapping(buf.as_ptr(), data.add(self.len), buf.len());
+        }
+        self.len += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(not(Py_LIMITED_API))]
+impl Drop for PyBytesWriter<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::Py_XDECREF(self.bytes) }
+    }
+}
+
 impl<'a> IntoPy<PyObject> for &'a [u8] {
     fn into_py(self, py: Python) -> PyObject {
         PyBytes::new(py, self).to_object(py)
@@ -136,6 +269,38 @@ mod tests {
         });
     }
 
+    #[cfg(not(Py_LIMITED_API))]
+    #[test]
+    fn test_bytes_writer() {
+        use super::PyBytesWriter;
+        use std::io::Write;
+
+        Python::with_gil(|py| {
+            let mut writer = PyBytesWriter::with_capacity(py, 4).unwrap();
+            assert_eq!(writer.capacity(), 4);
+            writer.write_all(b"abc").unwrap();
+            writer.write_all(b"defg").unwrap();
+            assert_eq!(writer.len(), 7);
+            assert!(writer.capacity() >= 7);
+            for _ in 0..1000 {
+                writer.write_all(b"0123456789").unwrap();
+            }
+            let bytes = writer.finish().unwrap();
+            assert_eq!(bytes.as_bytes().len(), 10007);
+            assert!(bytes.as_bytes().starts_with(b"abcdefg0123"));
+            assert!(bytes.as_bytes().ends_with(b"789"));
+
+            let empty = PyBytesWriter::new(py).unwrap();
+            assert!(empty.is_empty());
+            assert_eq!(empty.finish().unwrap().as_bytes(), b"");
+
+            // dropping an unfinished writer releases its bytes object
+            let mut unfinished = PyBytesWriter::new(py).unwrap();
+            unfinished.write_all(b"discarded").unwrap();
+            drop(unfinished);
+        });
+    }
+
     #[test]
     fn test_bytes_new_with() -> super::PyResult<()> {
         Python::with_gil(|py| -> super::PyResult<()> {
@@ -3,6 +3,7 @@
 //! For more details about these types, see the [Python
 //! documentation](https://docs.python.org/3/library/datetime.html)
 
+use crate::class::methods::{PyCFunction, PyMethodDef, PyMethodDefType, PyMethodType};
 use crate::err::PyResult;
 use crate::ffi;
 #[cfg(PyPy)]
@@ -404,6 +405,179 @@ pyobject_native_type!(
     #checkfunction=PyTZInfo_Check
 );
 
+impl PyTzInfo {
+    /// Returns the `datetime.timezone.utc` singleton.
+    pub fn utc(py: Python) -> PyResult<&PyTzInfo> {
+        #[cfg(all(Py_3_7, not(PyPy)))]
+        unsafe {
+            Ok(py.from_borrowed_ptr(PyDateTimeAPI.TimeZone_UTC))
+        }
+
+        #[cfg(not(all(Py_3_7, not(PyPy))))]
+        {
+            let utc = py.import("datetime")?.getattr("timezone")?.getattr("utc")?;
+            Ok(utc.downcast()?)
+        }
+    }
+
+    /// Creates a `datetime.timezone` with a fixed offset from UTC.
+    ///
+    /// `offset` must be strictly between `-timedelta(hours=24)` and `timedelta(hours=24)`,
+    /// otherwise `ValueError` is raised. A zero offset returns [`PyTzInfo::utc`].
+    pub fn timezone<'p>(py: Python<'p>, offset: &PyDelta) -> PyResult<&'p PyTzInfo> {
+        #[cfg(all(Py_3_7, not(PyPy)))]
+        unsafe {
+            let ptr = (PyDateTimeAPI.TimeZone_FromTimeZone)(offset.as_ptr(), ptr::null_mut());
+            py.from_owned_ptr_or_err(ptr)
+        }
+
+        #[cfg(not(all(Py_3_7, not(PyPy))))]
+        {
+            let timezone = py
+                .import("datetime")?
+                .getattr("timezone")?
+                .call1((offset,))?;
+            Ok(timezone.downcast()?)
+        }
+    }
+}
+
+/// Implements the methods of a `datetime.tzinfo` subclass defined in Rust.
+///
+/// Implementing this trait for a `#[pyclass(extends=PyTzInfo)]` adds the `utcoffset`, `dst` and
+/// `tzname` methods to the class, so they should not be defined in `#[pymethods]`. The methods
+/// inherited from `tzinfo`, such as `fromutc`, use these to convert between timezones.
+///
+/// The `dt` argument is the `datetime` being converted, or `None` when Python asks for an
+/// offset which doesn't depend on the date.
+///
+/// # Example
+///
+/// ```
+/// use pyo3::prelude::*;
+/// use pyo3::types::{PyDateTime, PyDelta, PyTzInfo, TzInfo};
+///
+/// #[pyclass(extends=PyTzInfo)]
+/// struct Cet;
+///
+/// impl TzInfo for Cet {
+///     fn utcoffset<'p>(
+///         &self,
+///         py: Python<'p>,
+///         _dt: Option<&'p PyDateTime>,
+///     ) -> PyResult<Option<&'p PyDelta>> {
+///         PyDelta::new(py, 0, 3600, 0, true).map(Some)
+///     }
+///
+///     fn tzname(&self, _py: Python, _dt: Option<&PyDateTime>) -> PyResult<Option<String>> {
+///         Ok(Some("CET".to_owned()))
+///     }
+/// }
+/// ```
+pub trait TzInfo: crate::PyClass {
+    /// The offset of local time from UTC, positive east of UTC, as returned by
+    /// `tzinfo.utcoffset(dt)`.
+    fn utcoffset<'p>(
+        &self,
+        py: Python<'p>,
+        dt: Option<&'p PyDateTime>,
+    ) -> PyResult<Option<&'p PyDelta>>;
+
+    /// The daylight saving time adjustment included in `utcoffset`, as returned by
+    /// `tzinfo.dst(dt)`.
+    ///
+    /// Defaults to a zero `timedelta`, which is correct for timezones without daylight saving
+    /// time and required by `tzinfo.fromutc`.
+    fn dst<'p>(
+        &self,
+        py: Python<'p>,
+        _dt: Option<&'p PyDateTime>,
+    ) -> PyResult<Option<&'p PyDelta>> {
+        PyDelta::new(py, 0, 0, 0, false).map(Some)
+    }
+
+    /// The name of the timezone, as returned by `tzinfo.tzname(dt)`. Defaults to `None`.
+    fn tzname(&self, _py: Python, _dt: Option<&PyDateTime>) -> PyResult<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// The method definitions added to a `#[pyclass]` implementing [`TzInfo`].
+#[doc(hidden)]
+pub struct TzInfoMethods<T>(std::marker::PhantomData<T>);
+
+impl<T: TzInfo> TzInfoMethods<T> {
+    pub const METHOD_DEFS: &'static [PyMethodDefType] = &[
+        PyMethodDefType::Method(PyMethodDef {
+            ml_name: "utcoffset\0",
+            ml_meth: PyMethodType::PyCFunction(PyCFunction(tzinfo_utcoffset::<T>)),
+            ml_flags: ffi::METH_O,
+            ml_doc: "\0",
+        }),
+        PyMethodDefType::Method(PyMethodDef {
+            ml_name: "dst\0",
+            ml_meth: PyMethodType::PyCFunction(PyCFunction(tzinfo_dst::<T>)),
+            ml_flags: ffi::METH_O,
+            ml_doc: "\0",
+        }),
+        PyMethodDefType::Method(PyMethodDef {
+            ml_name: "tzname\0",
+            ml_meth: PyMethodType::PyCFunction(PyCFunction(tzinfo_tzname::<T>)),
+            ml_flags: ffi::METH_O,
+            ml_doc: "\0",
+        }),
+    ];
+}
+
+/// Borrows the `TzInfo` implementation and the `dt` argument of a `tzinfo` method call.
+unsafe fn tzinfo_args<'p, T: TzInfo>(
+    py: Python<'p>,
+    slf: *mut ffi::PyObject,
+    dt: *mut ffi::PyObject,
+) -> PyResult<(crate::PyRef<'p, T>, Option<&'p PyDateTime>)> {
+    let cell = py.from_borrowed_ptr::<crate::PyCell<T>>(slf);
+    let dt = py.from_borrowed_ptr::<PyAny>(dt);
+    let dt =
+        if dt.is_none() {
+            None
+        } else {
+            Some(dt.downcast::<PyDateTime>().map_err(|err| {
+                crate::derive_utils::argument_extraction_error(py, "dt", err.into())
+            })?)
+        };
+    Ok((cell.try_borrow()?, dt))
+}
+
+unsafe extern "C" fn tzinfo_utcoffset<T: TzInfo>(
+    slf: *mut ffi::PyObject,
+    dt: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback_body!(py, {
+        let (slf, dt) = tzinfo_args::<T>(py, slf, dt)?;
+        slf.utcoffset(py, dt)
+    })
+}
+
+unsafe extern "C" fn tzinfo_dst<T: TzInfo>(
+    slf: *mut ffi::PyObject,
+    dt: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback_body!(py, {
+        let (slf, dt) = tzinfo_args::<T>(py, slf, dt)?;
+        slf.dst(py, dt)
+    })
+}
+
+unsafe extern "C" fn tzinfo_tzname<T: TzInfo>(
+    slf: *mut ffi::PyObject,
+    dt: *mut ffi::PyObject,
+) -> *mut ffi::PyObject {
+    crate::callback_body!(py, {
+        let (slf, dt) = tzinfo_args::<T>(py, slf, dt)?;
+        slf.tzname(py, dt)
+    })
+}
+
 /// Bindings for `datetime.timedelta`
 #[repr(transparent)]
 pub struct PyDelta(PyAny);
@@ -8,15 +8,16 @@ use crate::{
     AsPyPointer, IntoPy, IntoPyPointer, PyAny, PyNativeType, PyObject, Python, ToBorrowedObject,
     ToPyObject,
 };
+use std::collections::{BinaryHeap, LinkedList, VecDeque};
 
 /// Represents a Python `list`.
 #[repr(transparent)]
 pub struct PyList(PyAny);
 
-pyobject_native_type_core!(PyList, ffi::PyList_Type, #checkfunction=ffi::PyList_Check);
+pyobject_native_type!(PyList, ffi::PyListObject, ffi::PyList_Type, #checkfunction=ffi::PyList_Check);
 
 #[inline]
-unsafe fn new_from_iter<T>(
+pub(crate) unsafe fn new_from_iter<T>(
     elements: impl ExactSizeIterator<Item = T>,
     convert: impl Fn(T) -> PyObject,
 ) -> *mut ffi::PyObject {
@@ -206,6 +207,37 @@ where
     }
 }
 
+macro_rules! list_conversion_impl {
+    ($collection:ident) => {
+        impl<T> ToPyObject for $collection<T>
+        where
+            T: ToPyObject,
+        {
+            fn to_object(&self, py: Python<'_>) -> PyObject {
+                unsafe {
+                    PyObject::from_owned_ptr(py, new_from_iter(self.iter(), |e| e.to_object(py)))
+                }
+            }
+        }
+
+        impl<T> IntoPy<PyObject> for $collection<T>
+        where
+            T: IntoPy<PyObject>,
+        {
+            fn into_py(self, py: Python) -> PyObject {
+                unsafe {
+                    PyObject::from_owned_ptr(py, new_from_iter(self.into_iter(), |e| e.into_py(py)))
+                }
+            }
+        }
+    };
+}
+
+list_conversion_impl!(VecDeque);
+list_conversion_impl!(LinkedList);
+// Converts to a list in the internal heap order, like `BinaryHeap::iter`
+list_conversion_impl!(BinaryHeap);
+
 #[cfg(test)]
 mod tests {
     use crate::types::PyList;
@@ -431,4 +463,24 @@ mod tests {
             assert_eq!(2, list.get_item(1).extract::<i32>().unwrap());
         });
     }
+
+    #[test]
+    fn test_collections_to_list() {
+        use std::collections::{BinaryHeap, LinkedList, VecDeque};
+        Python::with_gil(|py| {
+            let deque: VecDeque<i32> = vec![1, 2, 3].into();
+            let list: &PyList = deque.to_object(py).into_ref(py).downcast().unwrap();
+            assert_eq!(list.extract::<Vec<i32>>().unwrap(), vec![1, 2, 3]);
+
+            let linked: LinkedList<i32> = vec![4, 5].into_iter().collect();
+            let obj = linked.into_py(py);
+            assert_eq!(obj.extract::<Vec<i32>>(py).unwrap(), vec![4, 5]);
+
+            let heap: BinaryHeap<i32> = vec![1, 5, 3].into();
+            let expected: Vec<i32> = heap.iter().cloned().collect();
+            let obj = heap.into_py(py);
+            assert_eq!(obj.extract::<Vec<i32>>(py).unwrap(), expected);
+            assert_eq!(expected[0], 5);
+        });
+    }
 }
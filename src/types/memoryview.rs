@@ -0,0 +1,262 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+#[cfg(not(Py_LIMITED_API))]
+use crate::buffer::{Element, PyBuffer};
+use crate::types::PyBytes;
+#[cfg(not(Py_LIMITED_API))]
+use crate::Python;
+use crate::{ffi, AsPyPointer, PyAny, PyErr, PyNativeType, PyResult};
+
+/// Represents a Python `memoryview`.
+#[repr(transparent)]
+pub struct PyMemoryView(PyAny);
+
+pyobject_native_type_core!(PyMemoryView, ffi::PyMemoryView_Type, #checkfunction=ffi::PyMemoryView_Check);
+
+impl PyMemoryView {
+    /// Creates a new memoryview of an object supporting the buffer protocol.
+    ///
+    /// This is equivalent to the Python expression `memoryview(obj)`.
+    pub fn from_object(obj: &PyAny) -> PyResult<&PyMemoryView> {
+        unsafe {
+            obj.py()
+                .from_owned_ptr_or_err(ffi::PyMemoryView_FromObject(obj.as_ptr()))
+        }
+    }
+
+    /// Creates a new read-only memoryview of `data` without copying it, which keeps `owner`
+    /// alive until the memoryview and all views derived from it are released.
+    ///
+    /// For a `#[pyclass]`, implementing
+    /// [`BufferExport`](crate::class::buffer::BufferExport) and calling
+    /// [`from_object`](PyMemoryView::from_object) is the safe alternative to this function.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be memory owned by `owner`, which stays valid and unmodified for as long as
+    /// `owner` is alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pyo3::types::{PyBytes, PyMemoryView};
+    /// use pyo3::Python;
+    ///
+    /// Python::with_gil(|py| {
+    ///     let bytes = PyBytes::new(py, b"header:payload");
+    ///     // Safety: the contents of `bytes` are immutable and live as long as `bytes`
+    ///     let payload =
+    ///         unsafe { PyMemoryView::from_slice(py, bytes, &bytes.as_bytes()[7..]) }.unwrap();
+    ///     assert_eq!(payload.tobytes().unwrap().as_bytes(), b"payload");
+    /// });
+    /// ```
+    #[cfg(not(Py_LIMITED_API))]
+    pub unsafe fn from_slice<'p>(
+        py: Python<'p>,
+        owner: &'p PyAny,
+        data: &[u8],
+    ) -> PyResult<&'p PyMemoryView> {
+        let exporter = slice_owner::new(py, owner, data)?;
+        py.from_owned_ptr_or_err(ffi::PyMemoryView_FromObject(exporter.as_ptr()))
+    }
+
+    /// Releases the underlying buffer, after which the memoryview can no longer be used.
+    ///
+    /// Returns `BufferError` if views derived from this memoryview still use the buffer.
+    pub fn release(&self) -> PyResult<()> {
+        self.call_method0("release").map(drop)
+    }
+
+    /// Copies the contents of the memoryview, in C-style order, into a new `bytes` object.
+    pub fn tobytes(&self) -> PyResult<&PyBytes> {
+        self.call_method0("tobytes")?
+            .downcast()
+            .map_err(PyErr::from)
+    }
+
+    /// Returns a view of the same memory with a different format and, optionally, shape.
+    ///
+    /// This is equivalent to the Python expression `self.cast(format, shape)`.
+    pub fn cast(&self, format: &str, shape: Option<&[usize]>) -> PyResult<&PyMemoryView> {
+        let view = match shape {
+            Some(shape) => self.call_method1("cast", (format, shape.to_vec()))?,
+            None => self.call_method1("cast", (format,))?,
+        };
+        view.downcast().map_err(PyErr::from)
+    }
+
+    /// Gets the buffer of this memoryview, to access its memory from Rust.
+    #[cfg(not(Py_LIMITED_API))]
+    pub fn buffer<T: Element>(&self) -> PyResult<PyBuffer<T>> {
+        PyBuffer::get(self)
+    }
+}
+
+/// A minimal object exporting a borrowed slice through the buffer protocol, while holding a
+/// reference to the object which owns the slice.
+#[cfg(not(Py_LIMITED_API))]
+mod slice_owner {
+    use crate::once_cell::GILOnceCell;
+    use crate::{ffi, AsPyPointer, PyAny, PyErr, PyObject, PyResult, Python};
+    use std::os::raw::{c_int, c_void};
+    use std::ptr;
+
+    #[repr(C)]
+    struct SliceOwner {
+        ob_base: ffi::PyObject,
+        owner: *mut ffi::PyObject,
+        buf: *mut c_void,
+        len: ffi::Py_ssize_t,
+    }
+
+    pub(super) unsafe fn new(py: Python, owner: &PyAny, data: &[u8]) -> PyResult<PyObject> {
+        let ty = slice_owner_type(py)?;
+        let obj: PyObject = PyObject::from_owned_ptr_or_err(py, ffi::PyType_GenericAlloc(ty, 0))?;
+        let slf = obj.as_ptr() as *mut SliceOwner;
+        ffi::Py_INCREF(owner.as_ptr());
+        (*slf).owner = owner.as_ptr();
+        (*slf).buf = data.as_ptr() as *mut c_void;
+        (*slf).len = data.len() as ffi::Py_ssize_t;
+        Ok(obj)
+    }
+
+    fn slice_owner_type(py: Python) -> PyResult<*mut ffi::PyTypeObject> {
+        static TYPE_OBJECT: GILOnceCell<usize> = GILOnceCell::new();
+        if let Some(&ty) = TYPE_OBJECT.get(py) {
+            return Ok(ty as *mut ffi::PyTypeObject);
+        }
+        let mut slots = [
+            ffi::PyType_Slot {
+                slot: ffi::Py_tp_dealloc,
+                pfunc: dealloc as *mut c_void,
+            },
+            ffi::PyType_Slot {
+                slot: ffi::Py_bf_getbuffer,
+                pfunc: getbuffer as *mut c_void,
+            },
+            ffi::PyType_Slot {
+                slot: 0,
+                pfunc: ptr::null_mut(),
+            },
+        ];
+        let mut spec = ffi::PyType_Spec {
+            name: "pyo3_runtime.SliceOwner\0".as_ptr() as *const _,
+            basicsize: std::mem::size_of::<SliceOwner>() as c_int,
+            itemsize: 0,
+            flags: ffi::Py_TPFLAGS_DEFAULT as _,
+            slots: slots.as_mut_ptr(),
+        };
+        let ty = unsafe { ffi::PyType_FromSpec(&mut spec) } as *mut ffi::PyTypeObject;
+        if ty.is_null() {
+            return Err(PyErr::api_call_failed(py));
+        }
+        // Setting buffer protocols via slots doesn't work until Python 3.9
+        if cfg!(not(Py_3_9)) {
+            unsafe { (*(*ty).tp_as_buffer).bf_getbuffer = Some(getbuffer) };
+        }
+        // the type object is kept alive for the rest of the program
+        Ok(*TYPE_OBJECT.get_or_init(py, || ty as usize) as *mut ffi::PyTypeObject)
+    }
+
+    unsafe extern "C" fn getbuffer(
+        slf: *mut ffi::PyObject,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> c_int {
+        let owner = slf as *mut SliceOwner;
+        ffi::PyBuffer_FillInfo(view, slf, (*owner).buf, (*owner).len, 1, flags)
+    }
+
+    unsafe extern "C" fn dealloc(slf: *mut ffi::PyObject) {
+        let ty = ffi::Py_TYPE(slf);
+        ffi::Py_XDECREF((*(slf as *mut SliceOwner)).owner);
+        (*ty).tp_free.unwrap()(slf as *mut c_void);
+        // instances of heap types hold a reference to their type since Python 3.8
+        if cfg!(Py_3_8) {
+            ffi::Py_DECREF(ty as *mut ffi::PyObject);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PyMemoryView;
+    use crate::exceptions::{PyBufferError, PyTypeError};
+    use crate::types::{IntoPyDict, PyByteArray, PyBytes};
+    use crate::{py_run, Python, ToPyObject};
+
+    #[test]
+    fn test_from_object() {
+        Python::with_gil(|py| {
+            let bytearray = PyByteArray::new(py, b"abcd");
+            let view = PyMemoryView::from_object(bytearray).unwrap();
+            assert_eq!(view.tobytes().unwrap().as_bytes(), b"abcd");
+            py_run!(py, view bytearray, "view[0] = ord('x'); assert bytearray == b'xbcd'");
+
+            // a resizable object cannot be resized while the view exists
+            let locals = [("bytearray", bytearray)].into_py_dict(py);
+            let err = py
+                .run("bytearray.append(0)", None, Some(locals))
+                .unwrap_err();
+            assert!(err.is_instance::<PyBufferError>(py));
+            view.release().unwrap();
+            py_run!(py, bytearray, "bytearray.append(0)");
+
+            let err = PyMemoryView::from_object(5.to_object(py).as_ref(py)).unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_cast() {
+        Python::with_gil(|py| {
+            let bytes = PyBytes::new(py, &[1, 0, 2, 0, 3, 0, 4, 0]);
+            let view = PyMemoryView::from_object(bytes).unwrap();
+            let matrix = view.cast("H", Some(&[2, 2])).unwrap();
+            py_run!(py, matrix, "assert matrix.tolist() == [[1, 2], [3, 4]]");
+            let buffer = matrix.buffer::<u16>().unwrap();
+            assert_eq!(buffer.shape(), [2, 2]);
+            assert_eq!(buffer.to_vec(py).unwrap(), [1, 2, 3, 4]);
+            buffer.release(py);
+
+            let flat = view.cast("I", None).unwrap();
+            assert_eq!(flat.buffer::<u32>().unwrap().item_count(), 2);
+            let err = view.cast("I", Some(&[3])).unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+        });
+    }
+
+    #[test]
+    fn test_from_slice() {
+        Python::with_gil(|py| {
+            let owner = PyBytes::new(py, b"header:payload");
+            let refcnt = owner.get_refcnt();
+            let view =
+                unsafe { PyMemoryView::from_slice(py, owner, &owner.as_bytes()[7..]) }.unwrap();
+            assert!(owner.get_refcnt() > refcnt);
+            assert_eq!(view.tobytes().unwrap().as_bytes(), b"payload");
+            py_run!(
+                py,
+                view,
+                r#"
+assert view.readonly
+assert view[1:3] == b"ay"
+try:
+    view[0] = 0
+except TypeError:
+    pass
+else:
+    assert False
+"#
+            );
+            let sub = view.cast("B", Some(&[7])).unwrap();
+            view.release().unwrap();
+            assert!(
+                owner.get_refcnt() > refcnt,
+                "the cast view keeps the owner alive"
+            );
+            sub.release().unwrap();
+            assert_eq!(owner.get_refcnt(), refcnt);
+        });
+    }
+}
@@ -6,20 +6,31 @@ pub use self::any::PyAny;
 pub use self::boolobject::PyBool;
 pub use self::bytearray::PyByteArray;
 pub use self::bytes::PyBytes;
+#[cfg(not(Py_LIMITED_API))]
+#[cfg_attr(docsrs, doc(cfg(not(Py_LIMITED_API))))]
+pub use self::bytes::PyBytesWriter;
 pub use self::complex::PyComplex;
 #[cfg(not(Py_LIMITED_API))]
+#[doc(hidden)]
+pub use self::datetime::TzInfoMethods;
+#[cfg(not(Py_LIMITED_API))]
 #[cfg_attr(docsrs, doc(cfg(not(Py_LIMITED_API))))]
 pub use self::datetime::{
     PyDate, PyDateAccess, PyDateTime, PyDelta, PyDeltaAccess, PyTime, PyTimeAccess, PyTzInfo,
+    TzInfo,
 };
 pub use self::dict::{IntoPyDict, PyDict};
 pub use self::floatob::PyFloat;
 pub use self::function::{PyCFunction, PyFunction};
 pub use self::iterator::PyIterator;
 pub use self::list::PyList;
+pub use self::memoryview::PyMemoryView;
 pub use self::module::PyModule;
 pub use self::num::PyLong;
 pub use self::num::PyLong as PyInt;
+pub use self::range::PyRange;
+#[cfg(any(feature = "smallvec", all(feature = "arrayvec", min_const_generics)))]
+pub(crate) use self::sequence::extract_sequence_into;
 pub use self::sequence::PySequence;
 pub use self::set::{PyFrozenSet, PySet};
 pub use self::slice::{PySlice, PySliceIndices};
@@ -193,6 +204,7 @@ macro_rules! pyobject_native_type_sized {
             type BaseNativeType = $name;
             type ThreadChecker = $crate::class::impl_::ThreadCheckerStub<$crate::PyObject>;
             type Initializer = $crate::pyclass_init::PyNativeTypeInitializer<Self>;
+            type Metaclass = $crate::types::PyType;
         }
     }
 }
@@ -209,6 +221,26 @@ macro_rules! pyobject_native_type {
     };
 }
 
+/// Declares all of the boilerplate for Python types which can be inherited from, but whose
+/// instances have a variable size, like `tuple`.
+#[macro_export]
+macro_rules! pyobject_native_var_type {
+    ($name:ty, $typeobject:expr $(, #module=$module:expr)? $(, #checkfunction=$checkfunction:path)? $(;$generics:ident)*) => {
+        $crate::pyobject_native_type_core!($name, $typeobject $(, #module=$module)? $(, #checkfunction=$checkfunction)? $(;$generics)*);
+        // To prevent inheriting native types with ABI3
+        #[cfg(not(Py_LIMITED_API))]
+        impl<'a, $($generics,)*> $crate::class::impl_::PyClassBaseType for $name {
+            type Dict = $crate::pyclass_slots::PyClassDummySlot;
+            type WeakRef = $crate::pyclass_slots::PyClassDummySlot;
+            type LayoutAsBase = $crate::pycell::PyCellVarBase<$name>;
+            type BaseNativeType = $name;
+            type ThreadChecker = $crate::class::impl_::ThreadCheckerStub<$crate::PyObject>;
+            type Initializer = $crate::pyclass_init::PyNativeTypeInitializer<Self>;
+            type Metaclass = $crate::types::PyType;
+        }
+    };
+}
+
 mod any;
 mod boolobject;
 mod bytearray;
@@ -222,8 +254,10 @@ mod floatob;
 mod function;
 mod iterator;
 mod list;
+mod memoryview;
 mod module;
 mod num;
+mod range;
 mod sequence;
 mod set;
 mod slice;
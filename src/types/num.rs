@@ -8,6 +8,10 @@ use crate::{
 };
 use std::convert::TryFrom;
 use std::i64;
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+};
 use std::os::raw::c_long;
 
 fn err_if_invalid_value<T: PartialEq>(
@@ -55,7 +59,7 @@ macro_rules! int_fits_larger_int {
 #[repr(transparent)]
 pub struct PyLong(PyAny);
 
-pyobject_native_type_core!(PyLong, ffi::PyLong_Type, #checkfunction=ffi::PyLong_Check);
+pyobject_native_var_type!(PyLong, ffi::PyLong_Type, #checkfunction=ffi::PyLong_Check);
 
 macro_rules! int_fits_c_long {
     ($rust_type:ty) => {
@@ -273,6 +277,64 @@ mod slow_128bit_int_conversion {
     int_convert_128!(u128, u64);
 }
 
+macro_rules! nonzero_int_impl {
+    ($nonzero_type:ty, $primitive_type:ty) => {
+        impl ToPyObject for $nonzero_type {
+            #[inline]
+            fn to_object(&self, py: Python) -> PyObject {
+                self.get().into_py(py)
+            }
+        }
+        impl IntoPy<PyObject> for $nonzero_type {
+            #[inline]
+            fn into_py(self, py: Python) -> PyObject {
+                self.get().into_py(py)
+            }
+        }
+
+        impl<'source> FromPyObject<'source> for $nonzero_type {
+            fn extract(obj: &'source PyAny) -> PyResult<Self> {
+                let val: $primitive_type = obj.extract()?;
+                <$nonzero_type>::new(val)
+                    .ok_or_else(|| exceptions::PyValueError::new_err("invalid zero value"))
+            }
+        }
+    };
+}
+
+nonzero_int_impl!(NonZeroI8, i8);
+nonzero_int_impl!(NonZeroI16, i16);
+nonzero_int_impl!(NonZeroI32, i32);
+nonzero_int_impl!(NonZeroI64, i64);
+nonzero_int_impl!(NonZeroI128, i128);
+nonzero_int_impl!(NonZeroIsize, isize);
+nonzero_int_impl!(NonZeroU8, u8);
+nonzero_int_impl!(NonZeroU16, u16);
+nonzero_int_impl!(NonZeroU32, u32);
+nonzero_int_impl!(NonZeroU64, u64);
+nonzero_int_impl!(NonZeroU128, u128);
+nonzero_int_impl!(NonZeroUsize, usize);
+
+impl<T: ToPyObject> ToPyObject for Wrapping<T> {
+    #[inline]
+    fn to_object(&self, py: Python) -> PyObject {
+        self.0.to_object(py)
+    }
+}
+
+impl<T: IntoPy<PyObject>> IntoPy<PyObject> for Wrapping<T> {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.0.into_py(py)
+    }
+}
+
+impl<'source, T: FromPyObject<'source>> FromPyObject<'source> for Wrapping<T> {
+    fn extract(obj: &'source PyAny) -> PyResult<Self> {
+        obj.extract().map(Wrapping)
+    }
+}
+
 #[cfg(test)]
 mod test_128bit_intergers {
     use super::*;
@@ -455,4 +517,38 @@ mod tests {
     test_common!(i128, i128);
     #[cfg(not(any(Py_LIMITED_API, PyPy)))]
     test_common!(u128, u128);
+
+    #[test]
+    fn test_nonzero() {
+        use std::num::{NonZeroI64, NonZeroU32};
+        Python::with_gil(|py| {
+            let v = NonZeroU32::new(42).unwrap();
+            let obj = v.to_object(py);
+            assert_eq!(obj.extract::<u32>(py).unwrap(), 42);
+            assert_eq!(obj.extract::<NonZeroU32>(py).unwrap(), v);
+
+            let obj = (-1).to_object(py);
+            assert_eq!(obj.extract::<NonZeroI64>(py).unwrap().get(), -1);
+            assert!(obj
+                .extract::<NonZeroU32>(py)
+                .unwrap_err()
+                .is_instance::<crate::exceptions::PyOverflowError>(py));
+
+            let zero = 0.to_object(py);
+            let err = zero.extract::<NonZeroI64>(py).unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_wrapping() {
+        use std::num::Wrapping;
+        Python::with_gil(|py| {
+            let v = Wrapping(std::u64::MAX);
+            let obj = v.to_object(py);
+            assert_eq!(obj.extract::<u64>(py).unwrap(), std::u64::MAX);
+            assert_eq!(obj.extract::<Wrapping<u64>>(py).unwrap(), v);
+            assert!(obj.extract::<Wrapping<u32>>(py).is_err());
+        });
+    }
 }
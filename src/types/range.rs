@@ -0,0 +1,343 @@
+// Copyright (c) 2017-present PyO3 Project and Contributors
+
+use crate::exceptions::{PyOverflowError, PyValueError};
+use crate::{
+    ffi, AsPyPointer, FromPyObject, IntoPy, PyAny, PyErr, PyNativeType, PyObject, PyResult, Python,
+    ToPyObject,
+};
+use std::convert::TryFrom;
+use std::iter::StepBy;
+use std::ops::{Range, RangeInclusive};
+
+/// Represents a Python `range`.
+///
+/// Ranges are created lazily by Python, so returning a `Range<i64>` from a function does not
+/// allocate a list of all its elements.
+#[repr(transparent)]
+pub struct PyRange(PyAny);
+
+pyobject_native_type_core!(PyRange, ffi::PyRange_Type, #checkfunction=ffi::PyRange_Check);
+
+impl PyRange {
+    /// Creates a new Python `range(start, stop)`.
+    pub fn new(py: Python, start: i64, stop: i64) -> PyResult<&PyRange> {
+        Self::new_with_step(py, start, stop, 1)
+    }
+
+    /// Creates a new Python `range(start, stop, step)`.
+    ///
+    /// Returns `ValueError` if `step` is zero.
+    pub fn new_with_step(py: Python, start: i64, stop: i64, step: i64) -> PyResult<&PyRange> {
+        new_range(py, start, i128::from(stop), i128::from(step))
+    }
+
+    /// Returns the value of the `start` attribute.
+    pub fn start(&self) -> PyResult<i64> {
+        self.getattr("start")?.extract()
+    }
+
+    /// Returns the value of the `stop` attribute.
+    pub fn stop(&self) -> PyResult<i64> {
+        self.getattr("stop")?.extract()
+    }
+
+    /// Returns the value of the `step` attribute.
+    pub fn step(&self) -> PyResult<i64> {
+        self.getattr("step")?.extract()
+    }
+
+    /// Returns the number of elements in the range.
+    ///
+    /// This is equivalent to the Python expression `len(self)`, and raises `OverflowError` for
+    /// ranges with more than `isize::MAX` elements.
+    pub fn len(&self) -> PyResult<usize> {
+        let len = unsafe { ffi::PyObject_Size(self.as_ptr()) };
+        if len == -1 {
+            Err(PyErr::api_call_failed(self.py()))
+        } else {
+            Ok(len as usize)
+        }
+    }
+
+    /// Checks whether the range is empty.
+    pub fn is_empty(&self) -> PyResult<bool> {
+        self.len().map(|len| len == 0)
+    }
+}
+
+fn new_range(py: Python, start: i64, stop: i128, step: i128) -> PyResult<&PyRange> {
+    let range = py.get_type::<PyRange>().call1((start, stop, step))?;
+    range.downcast().map_err(PyErr::from)
+}
+
+impl ToPyObject for Range<i64> {
+    fn to_object(&self, py: Python) -> PyObject {
+        new_range(py, self.start, i128::from(self.end), 1)
+            .expect("failed to create range")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for Range<i64> {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+impl ToPyObject for RangeInclusive<i64> {
+    fn to_object(&self, py: Python) -> PyObject {
+        // the end of `..=i64::MAX` does not fit into an `i64`
+        new_range(py, *self.start(), i128::from(*self.end()) + 1, 1)
+            .expect("failed to create range")
+            .into()
+    }
+}
+
+impl IntoPy<PyObject> for RangeInclusive<i64> {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+/// Converts a `StepBy<Range<i64>>` to a Python `range` with the same elements.
+///
+/// # Panics
+///
+/// Panics if more than `usize::MAX` elements are left, which is only possible on targets where
+/// `usize` is narrower than 64 bits.
+impl ToPyObject for StepBy<Range<i64>> {
+    fn to_object(&self, py: Python) -> PyObject {
+        let mut iter = self.clone();
+        let range = match (iter.next(), iter.next()) {
+            (Some(first), Some(second)) => {
+                // the step of `i64::MIN..i64::MAX` does not fit into an `i64`
+                let step = i128::from(second) - i128::from(first);
+                let remaining = remaining_len(&iter, step as usize)
+                    .expect("StepBy range has too many elements to convert to range");
+                let stop = i128::from(first) + step * (remaining as i128 + 2);
+                new_range(py, first, stop, step)
+            }
+            (Some(first), None) => new_range(py, first, i128::from(first) + 1, 1),
+            (None, _) => new_range(py, 0, 0, 1),
+        };
+        range.expect("failed to create range").into()
+    }
+}
+
+/// Returns the number of elements left in `iter`, which advances by `step`, or `None` if there
+/// are more than `usize::MAX`.
+fn remaining_len(iter: &StepBy<Range<i64>>, step: usize) -> Option<usize> {
+    match iter.size_hint() {
+        // exact unless the underlying range has more than `usize::MAX` elements
+        (lower, Some(upper)) if lower == upper => Some(lower),
+        _ => search_len(iter, step),
+    }
+}
+
+/// Finds the number of elements left in `iter` by a binary search over `nth`, which skips
+/// elements of a `StepBy<Range<i64>>` without iterating over them as long as `(n + 1) * step`
+/// fits into a `usize`.
+fn search_len(iter: &StepBy<Range<i64>>, step: usize) -> Option<usize> {
+    let max = usize::MAX / step;
+    let mut rest = iter.clone();
+    if rest.nth(max - 1).is_some() {
+        return if rest.next().is_none() {
+            Some(max)
+        } else {
+            None
+        };
+    }
+    // the number of elements is within `low..=high`
+    let (mut low, mut high) = (0, max - 1);
+    while low < high {
+        let mid = low + (high - low) / 2 + 1;
+        if iter.clone().nth(mid - 1).is_some() {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Some(low)
+}
+
+impl IntoPy<PyObject> for StepBy<Range<i64>> {
+    #[inline]
+    fn into_py(self, py: Python) -> PyObject {
+        self.to_object(py)
+    }
+}
+
+fn non_unit_step_err(step: i64, target: &str) -> PyErr {
+    PyValueError::new_err(format!(
+        "cannot convert a range with step {} to {}",
+        step, target
+    ))
+}
+
+/// Returns `start + offset` if it fits into an `i64`.
+fn checked_end(start: i64, offset: i128) -> PyResult<i64> {
+    i64::try_from(i128::from(start) + offset)
+        .map_err(|_| PyOverflowError::new_err("the end of the range does not fit into an i64"))
+}
+
+impl FromPyObject<'_> for Range<i64> {
+    /// Extracts a `range` with a step of 1, or with at most one element.
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        let range: &PyRange = ob.downcast()?;
+        let (start, step) = (range.start()?, range.step()?);
+        if step == 1 {
+            return Ok(start..range.stop()?);
+        }
+        match range.len()? {
+            len @ 0..=1 => Ok(start..checked_end(start, len as i128)?),
+            _ => Err(non_unit_step_err(step, "Range")),
+        }
+    }
+}
+
+impl FromPyObject<'_> for RangeInclusive<i64> {
+    /// Extracts a non-empty `range` with a step of 1, or with exactly one element.
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        let range: &PyRange = ob.downcast()?;
+        let (start, step) = (range.start()?, range.step()?);
+        let len = if step == 1 {
+            // avoid `len()`, which overflows for `range(0, 2**63)`
+            (range.getattr("stop")?.extract::<i128>()? - i128::from(start)).max(0)
+        } else {
+            range.len()? as i128
+        };
+        match len {
+            0 => Err(PyValueError::new_err(
+                "cannot convert an empty range to RangeInclusive",
+            )),
+            1 => Ok(start..=start),
+            _ if step == 1 => Ok(start..=checked_end(start, len - 1)?),
+            _ => Err(non_unit_step_err(step, "RangeInclusive")),
+        }
+    }
+}
+
+impl FromPyObject<'_> for StepBy<Range<i64>> {
+    /// Extracts a `range` with a positive step.
+    fn extract(ob: &PyAny) -> PyResult<Self> {
+        let range: &PyRange = ob.downcast()?;
+        let step = range.step()?;
+        if step < 0 {
+            return Err(non_unit_step_err(step, "StepBy<Range>"));
+        }
+        let step = usize::try_from(step)
+            .map_err(|_| PyOverflowError::new_err("range step does not fit into a usize"))?;
+        Ok((range.start()?..range.stop()?).step_by(step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PyRange;
+    use crate::exceptions::{PyTypeError, PyValueError};
+    use crate::{IntoPy, PyObject, Python, ToPyObject};
+    use std::iter::StepBy;
+    use std::ops::{Range, RangeInclusive};
+
+    #[test]
+    fn test_new() {
+        Python::with_gil(|py| {
+            let range = PyRange::new(py, 1, 10).unwrap();
+            assert_eq!(range.start().unwrap(), 1);
+            assert_eq!(range.stop().unwrap(), 10);
+            assert_eq!(range.step().unwrap(), 1);
+            assert_eq!(range.len().unwrap(), 9);
+
+            let range = PyRange::new_with_step(py, 10, 0, -3).unwrap();
+            assert_eq!(range.len().unwrap(), 4);
+            assert!(PyRange::new(py, 5, 5).unwrap().is_empty().unwrap());
+
+            let err = PyRange::new_with_step(py, 0, 10, 0).unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_range_roundtrip() {
+        Python::with_gil(|py| {
+            let obj = (0..i64::MAX).to_object(py);
+            crate::py_run!(py, obj, "assert obj == range(0, 2**63 - 1)");
+            assert_eq!(obj.extract::<Range<i64>>(py).unwrap(), 0..i64::MAX);
+
+            let obj = (-5..=5i64).into_py(py);
+            crate::py_run!(py, obj, "assert obj == range(-5, 6)");
+            assert_eq!(obj.extract::<RangeInclusive<i64>>(py).unwrap(), -5..=5);
+
+            let obj = (i64::MIN..=i64::MAX).into_py(py);
+            crate::py_run!(py, obj, "assert obj == range(-2**63, 2**63)");
+            let obj = py.eval("range(0, 2**63)", None, None).unwrap();
+            assert_eq!(obj.extract::<RangeInclusive<i64>>().unwrap(), 0..=i64::MAX);
+        });
+    }
+
+    #[test]
+    fn test_step_by() {
+        Python::with_gil(|py| {
+            let obj = (1..10i64).step_by(3).into_py(py);
+            crate::py_run!(py, obj, "assert list(obj) == [1, 4, 7]");
+            let steps: StepBy<Range<i64>> = obj.extract(py).unwrap();
+            assert_eq!(steps.collect::<Vec<_>>(), [1, 4, 7]);
+
+            let obj = (3..4i64).step_by(5).into_py(py);
+            crate::py_run!(py, obj, "assert list(obj) == [3]");
+            let obj = (3..3i64).step_by(5).into_py(py);
+            crate::py_run!(py, obj, "assert list(obj) == []");
+
+            #[cfg(target_pointer_width = "64")]
+            {
+                let obj = (i64::MIN..i64::MAX).step_by(1 << 63).into_py(py);
+                crate::py_run!(py, obj, "assert obj == range(-2**63, 2**63, 2**63)");
+            }
+        });
+    }
+
+    #[test]
+    fn test_search_len() {
+        for (range, step) in vec![
+            (0..0, 1),
+            (0..10, 3),
+            (-5..5, 1),
+            (i64::MIN..i64::MAX, usize::MAX / 3),
+        ] {
+            let iter = range.step_by(step);
+            assert_eq!(super::search_len(&iter, step), Some(iter.clone().count()));
+        }
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(
+            super::search_len(&(i64::MIN..i64::MAX).step_by(1), 1),
+            Some(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn test_extract_errors() {
+        Python::with_gil(|py| {
+            let stepped = py.eval("range(0, 10, 2)", None, None).unwrap();
+            let err = stepped.extract::<Range<i64>>().unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+
+            let single = py.eval("range(4, 5, 7)", None, None).unwrap();
+            assert_eq!(single.extract::<Range<i64>>().unwrap(), 4..5);
+            assert_eq!(single.extract::<RangeInclusive<i64>>().unwrap(), 4..=4);
+
+            let empty = py.eval("range(0)", None, None).unwrap();
+            let err = empty.extract::<RangeInclusive<i64>>().unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+
+            let backwards = py.eval("range(10, 0, -1)", None, None).unwrap();
+            let err = backwards.extract::<StepBy<Range<i64>>>().unwrap_err();
+            assert!(err.is_instance::<PyValueError>(py));
+
+            let list: PyObject = vec![1, 2].into_py(py);
+            let err = list.extract::<Range<i64>>(py).unwrap_err();
+            assert!(err.is_instance::<PyTypeError>(py));
+        });
+    }
+}
@@ -1,11 +1,12 @@
 // Copyright (c) 2017-present PyO3 Project and Contributors
 
-use crate::err::{self, PyDowncastError, PyErr, PyResult};
+use crate::err::{self, ExtractionError, PyDowncastError, PyErr, PyResult};
 use crate::ffi::{self, Py_ssize_t};
 use crate::instance::PyNativeType;
 use crate::types::{PyAny, PyList, PyTuple};
 use crate::AsPyPointer;
 use crate::{FromPyObject, PyTryFrom, ToBorrowedObject};
+use std::collections::{BinaryHeap, LinkedList, VecDeque};
 
 /// Represents a reference to a Python object supporting the sequence protocol.
 #[repr(transparent)]
@@ -295,15 +296,83 @@ where
 }
 
 fn extract_sequence<'s, T>(obj: &'s PyAny) -> PyResult<Vec<T>>
+where
+    T: FromPyObject<'s>,
+{
+    extract_sequence_into(obj, Vec::with_capacity, |v, item| {
+        v.push(item);
+        Ok(())
+    })
+}
+
+/// Extracts the elements of a sequence into a collection created by `with_capacity` from the
+/// length of the sequence, adding each element with `push`.
+pub(crate) fn extract_sequence_into<'s, T, C>(
+    obj: &'s PyAny,
+    with_capacity: impl FnOnce(usize) -> C,
+    mut push: impl FnMut(&mut C, T) -> PyResult<()>,
+) -> PyResult<C>
 where
     T: FromPyObject<'s>,
 {
     let seq = <PySequence as PyTryFrom>::try_from(obj)?;
-    let mut v = Vec::with_capacity(seq.len().unwrap_or(0) as usize);
-    for item in seq.iter()? {
-        v.push(item?.extract::<T>()?);
+    let mut collection = with_capacity(seq.len().unwrap_or(0) as usize);
+    for (index, item) in seq.iter()?.enumerate() {
+        let item = item?
+            .extract::<T>()
+            .map_err(|err| ExtractionError::with_index(obj.py(), index, err))?;
+        push(&mut collection, item)?;
+    }
+    Ok(collection)
+}
+
+impl<'a, T> FromPyObject<'a> for VecDeque<T>
+where
+    T: FromPyObject<'a>,
+{
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        extract_sequence_into(obj, VecDeque::with_capacity, |v, item| {
+            v.push_back(item);
+            Ok(())
+        })
+    }
+}
+
+impl<'a, T> FromPyObject<'a> for LinkedList<T>
+where
+    T: FromPyObject<'a>,
+{
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        extract_sequence_into(
+            obj,
+            |_| LinkedList::new(),
+            |l, item| {
+                l.push_back(item);
+                Ok(())
+            },
+        )
+    }
+}
+
+impl<'a, T> FromPyObject<'a> for BinaryHeap<T>
+where
+    T: FromPyObject<'a> + Ord,
+{
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        extract_sequence_into(obj, BinaryHeap::with_capacity, |h, item| {
+            h.push(item);
+            Ok(())
+        })
+    }
+}
+
+impl<'a, T> FromPyObject<'a> for Box<[T]>
+where
+    T: FromPyObject<'a>,
+{
+    fn extract(obj: &'a PyAny) -> PyResult<Self> {
+        Vec::extract(obj).map(Vec::into_boxed_slice)
     }
-    Ok(v)
 }
 
 impl<'v> PyTryFrom<'v> for PySequence {
@@ -672,4 +741,27 @@ mod tests {
             assert!(empty_seq.is_empty().unwrap());
         });
     }
+
+    #[test]
+    fn test_extract_collections() {
+        use std::collections::{BinaryHeap, LinkedList, VecDeque};
+        Python::with_gil(|py| {
+            let ob = py.eval("(3, 1, 2)", None, None).unwrap();
+            let deque: VecDeque<i32> = ob.extract().unwrap();
+            assert_eq!(deque, [3, 1, 2]);
+            let linked: LinkedList<i32> = ob.extract().unwrap();
+            assert!(linked.iter().eq(&[3, 1, 2]));
+            let heap: BinaryHeap<i32> = ob.extract().unwrap();
+            assert_eq!(heap.into_sorted_vec(), [1, 2, 3]);
+            let boxed: Box<[i32]> = ob.extract().unwrap();
+            assert_eq!(&*boxed, &[3, 1, 2]);
+
+            let err = py
+                .eval("[1, 'a']", None, None)
+                .unwrap()
+                .extract::<VecDeque<i32>>()
+                .unwrap_err();
+            assert!(err.is_instance::<crate::exceptions::PyTypeError>(py));
+        });
+    }
 }
@@ -15,7 +15,7 @@ use std::str;
 #[repr(transparent)]
 pub struct PyString(PyAny);
 
-pyobject_native_type_core!(PyString, ffi::PyUnicode_Type, #checkfunction=ffi::PyUnicode_Check);
+pyobject_native_type!(PyString, ffi::PyUnicodeObject, ffi::PyUnicode_Type, #checkfunction=ffi::PyUnicode_Check);
 
 impl PyString {
     /// Creates a new Python string object.
@@ -12,6 +12,9 @@ use crate::{ffi, AsPyPointer, PyAny, Python};
 pub struct PyType(PyAny);
 
 pyobject_native_type_core!(PyType, ffi::PyType_Type, #checkfunction=ffi::PyType_Check);
+// Subclasses of `type` are metaclasses, see `PyMetaclass`
+#[cfg(not(any(Py_LIMITED_API, PyPy)))]
+pyobject_native_type_sized!(PyType, ffi::PyHeapTypeObject);
 
 impl PyType {
     /// Creates a new type object.
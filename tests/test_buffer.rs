@@ -1,7 +1,13 @@
 #![cfg(not(Py_LIMITED_API))]
 
 use pyo3::{
-    buffer::PyBuffer, class::PyBufferProtocol, exceptions::PyBufferError, ffi, prelude::*,
+    buffer::{Element, PyBuffer},
+    class::{BufferExport, PyBufferProtocol},
+    exceptions::PyBufferError,
+    ffi,
+    prelude::*,
+    py_run,
+    types::PyDict,
     AsPyPointer,
 };
 use std::{
@@ -143,3 +149,132 @@ fn test_get_buffer_errors() {
         );
     });
 }
+
+#[derive(Element, Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Point {
+    x: f64,
+    y: f64,
+    flags: i32,
+    id: i32,
+}
+
+#[derive(Element, Clone, Copy, Debug, PartialEq)]
+#[repr(C)]
+struct Tagged {
+    tag: u8,
+    value: f64,
+}
+
+#[test]
+fn test_derive_element_format() {
+    assert_eq!(
+        Point::layout().unwrap().to_string(),
+        "T{=d:x:=d:y:=i:flags:=i:id:}"
+    );
+    assert_eq!(
+        Tagged::layout().unwrap().to_string(),
+        "T{=B:tag:7x=d:value:}"
+    );
+    assert_eq!(
+        Tagged::layout().unwrap().size(),
+        std::mem::size_of::<Tagged>()
+    );
+}
+
+#[test]
+fn test_derive_element_from_ctypes() {
+    Python::with_gil(|py| {
+        let locals = PyDict::new(py);
+        py.run(
+            r#"
+import ctypes
+class Point(ctypes.Structure):
+    _fields_ = [("x", ctypes.c_double), ("y", ctypes.c_double),
+                ("flags", ctypes.c_int32), ("id", ctypes.c_int32)]
+# ctypes leaves `strides` unset, which `memoryview` fills in
+points = memoryview((Point * 2)(Point(1.0, 2.0, 3, 4), Point(-1.0, 0.5, 0, 7)))
+"#,
+            Some(locals),
+            None,
+        )
+        .unwrap();
+        let points = locals.get_item("points").unwrap();
+
+        let buffer = PyBuffer::<Point>::get(points).unwrap();
+        assert_eq!(buffer.item_count(), 2);
+        assert_eq!(
+            buffer.to_vec(py).unwrap(),
+            vec![
+                Point {
+                    x: 1.0,
+                    y: 2.0,
+                    flags: 3,
+                    id: 4
+                },
+                Point {
+                    x: -1.0,
+                    y: 0.5,
+                    flags: 0,
+                    id: 7
+                },
+            ]
+        );
+        buffer.release(py);
+
+        // a struct with a different layout is rejected
+        assert!(PyBuffer::<Tagged>::get(points).is_err());
+        assert!(PyBuffer::<f64>::get(points).is_err());
+    });
+}
+
+#[pyclass]
+struct TaggedValues {
+    values: Vec<Tagged>,
+}
+
+impl BufferExport for TaggedValues {
+    type Item = Tagged;
+
+    fn buffer(&self) -> &[Tagged] {
+        &self.values
+    }
+}
+
+#[test]
+fn test_derive_element_export() {
+    Python::with_gil(|py| {
+        let values = vec![
+            Tagged {
+                tag: 1,
+                value: 0.25,
+            },
+            Tagged {
+                tag: 2,
+                value: -8.0,
+            },
+        ];
+        let obj = Py::new(
+            py,
+            TaggedValues {
+                values: values.clone(),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            obj,
+            r#"
+import struct
+with memoryview(obj) as view:
+    assert view.itemsize == 16
+    assert view.format == "T{=B:tag:7x=d:value:}"
+    assert list(struct.iter_unpack("=B7xd", view.tobytes())) == [(1, 0.25), (2, -8.0)]
+"#
+        );
+
+        let buffer = PyBuffer::<Tagged>::get(obj.as_ref(py)).unwrap();
+        assert_eq!(buffer.to_vec(py).unwrap(), values);
+        buffer.release(py);
+    });
+}
@@ -1,10 +1,11 @@
 #![cfg(not(Py_LIMITED_API))]
 
 use pyo3::buffer::PyBuffer;
-use pyo3::class::PyBufferProtocol;
+use pyo3::class::{BufferExport, PyBufferProtocol};
 use pyo3::exceptions::PyBufferError;
 use pyo3::ffi;
 use pyo3::prelude::*;
+use pyo3::py_run;
 use pyo3::types::IntoPyDict;
 use pyo3::AsPyPointer;
 use std::ffi::CStr;
@@ -130,3 +131,304 @@ fn test_buffer_referenced() {
 
     assert!(drop_called.load(Ordering::Relaxed));
 }
+
+#[pyclass]
+struct ExportedVec {
+    data: Vec<i32>,
+    writable: bool,
+}
+
+impl BufferExport for ExportedVec {
+    type Item = i32;
+
+    fn buffer(&self) -> &[i32] {
+        &self.data
+    }
+
+    fn buffer_mut(&mut self) -> Option<&mut [i32]> {
+        if self.writable {
+            Some(&mut self.data)
+        } else {
+            None
+        }
+    }
+}
+
+#[pymethods]
+impl ExportedVec {
+    fn sum(&self) -> i32 {
+        self.data.iter().sum()
+    }
+
+    fn push(&mut self, value: i32) {
+        self.data.push(value)
+    }
+}
+
+#[test]
+fn test_buffer_export_readonly() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let vec = PyCell::new(
+        py,
+        ExportedVec {
+            data: vec![1, 2, 3],
+            writable: false,
+        },
+    )
+    .unwrap();
+
+    py_run!(
+        py,
+        vec,
+        r#"
+        view = memoryview(vec)
+        assert view.readonly
+        assert view.format == 'i' and view.itemsize == 4
+        assert view.shape == (3,) and view.tolist() == [1, 2, 3]
+        assert vec.sum() == 6
+        try:
+            vec.push(4)
+        except BufferError:
+            pass
+        else:
+            assert False, "push succeeded while the buffer was exported"
+        view.release()
+        vec.push(4)
+        assert bytes(vec) == bytes(memoryview(vec))
+    "#
+    );
+    py_expect_exception!(py, vec, "memoryview(vec)[0] = 5", PyTypeError);
+    assert_eq!(vec.borrow().data, [1, 2, 3, 4]);
+
+    let buffer = PyBuffer::<i32>::get(vec).unwrap();
+    assert!(buffer.readonly());
+    assert_eq!(buffer.to_vec(py).unwrap(), [1, 2, 3, 4]);
+    assert!(vec.try_borrow_mut().is_err());
+    drop(buffer);
+    assert!(vec.try_borrow_mut().is_ok());
+}
+
+#[test]
+fn test_buffer_export_writable() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let vec = PyCell::new(
+        py,
+        ExportedVec {
+            data: vec![1, 2, 3],
+            writable: true,
+        },
+    )
+    .unwrap();
+
+    py_run!(
+        py,
+        vec,
+        r#"
+        view = memoryview(vec)
+        assert not view.readonly
+        view[0] = 10
+        view[1] = 20
+        try:
+            vec.sum()
+        except BufferError:
+            pass
+        else:
+            assert False, "sum succeeded while a writable buffer was exported"
+        try:
+            memoryview(vec)
+        except BufferError:
+            pass
+        else:
+            assert False, "exported again while a writable buffer was exported"
+        view.release()
+        assert vec.sum() == 33
+        other = memoryview(vec)
+        other[2] = 30
+        other.release()
+        assert vec.sum() == 60
+    "#
+    );
+
+    // while Rust code borrows the object, only read-only buffers can be exported
+    let borrowed = vec.borrow();
+    let buffer = PyBuffer::<i32>::get(vec).unwrap();
+    assert!(buffer.readonly());
+    drop(buffer);
+    let mut view = std::mem::MaybeUninit::<ffi::Py_buffer>::uninit();
+    let result =
+        unsafe { ffi::PyObject_GetBuffer(vec.as_ptr(), view.as_mut_ptr(), ffi::PyBUF_WRITABLE) };
+    assert_eq!(result, -1);
+    assert!(PyErr::fetch(py).unwrap().is_instance::<PyBufferError>(py));
+    drop(borrowed);
+    assert!(vec.try_borrow_mut().is_ok());
+}
+
+#[pyclass]
+struct ExportedMatrix {
+    data: Vec<u16>,
+    strides: Option<Vec<isize>>,
+}
+
+impl BufferExport for ExportedMatrix {
+    type Item = u16;
+
+    fn buffer(&self) -> &[u16] {
+        &self.data
+    }
+
+    fn shape(&self) -> Option<Vec<usize>> {
+        Some(vec![2, 3])
+    }
+
+    fn strides(&self) -> Option<Vec<isize>> {
+        self.strides.clone()
+    }
+}
+
+#[test]
+fn test_buffer_export_shape_and_strides() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+    let matrix = PyCell::new(
+        py,
+        ExportedMatrix {
+            data: (0..6).collect(),
+            strides: None,
+        },
+    )
+    .unwrap();
+    py_assert!(
+        py,
+        matrix,
+        "memoryview(matrix).tolist() == [[0, 1, 2], [3, 4, 5]]"
+    );
+
+    // the transpose, in Fortran order
+    let transposed = PyCell::new(
+        py,
+        ExportedMatrix {
+            data: (0..6).collect(),
+            strides: Some(vec![2, 4]),
+        },
+    )
+    .unwrap();
+    py_assert!(
+        py,
+        transposed,
+        "memoryview(transposed).tolist() == [[0, 2, 4], [1, 3, 5]]"
+    );
+    py_assert!(
+        py,
+        transposed,
+        "bytes(transposed) == memoryview(transposed).tobytes(order='C')"
+    );
+    // consumers which cannot handle strides need a C-contiguous buffer
+    py_expect_exception!(
+        py,
+        transposed,
+        "import hashlib; hashlib.md5(transposed)",
+        PyBufferError
+    );
+
+    // reversed rows, through a negative stride
+    let reversed = PyCell::new(
+        py,
+        ExportedMatrix {
+            data: (0..6).collect(),
+            strides: Some(vec![6, -2]),
+        },
+    )
+    .unwrap();
+    py_assert!(
+        py,
+        reversed,
+        "memoryview(reversed).tolist() == [[2, 1, 0], [5, 4, 3]]"
+    );
+
+    let out_of_bounds = PyCell::new(
+        py,
+        ExportedMatrix {
+            data: (0..6).collect(),
+            strides: Some(vec![8, 2]),
+        },
+    )
+    .unwrap();
+    py_expect_exception!(
+        py,
+        out_of_bounds,
+        "memoryview(out_of_bounds)",
+        PyBufferError
+    );
+    let too_short = PyCell::new(
+        py,
+        ExportedMatrix {
+            data: (0..5).collect(),
+            strides: None,
+        },
+    )
+    .unwrap();
+    py_expect_exception!(py, too_short, "memoryview(too_short)", PyBufferError);
+    assert!(too_short.try_borrow_mut().is_ok());
+}
+
+#[test]
+fn test_buffer_view_of_export() {
+    Python::with_gil(|py| {
+        let transposed = PyCell::new(
+            py,
+            ExportedMatrix {
+                data: (0..6).collect(),
+                strides: Some(vec![2, 4]),
+            },
+        )
+        .unwrap();
+        let buffer = PyBuffer::<u16>::get(transposed).unwrap();
+        let view = buffer.as_view(py);
+        assert_eq!(view.shape(), [2, 3]);
+        assert!(view.as_slice().is_none());
+        assert_eq!(view.to_vec(), [0, 2, 4, 1, 3, 5]);
+        assert_eq!(view.transpose().unwrap().as_slice().unwrap().len(), 6);
+        assert_eq!(view.rows().nth(1).unwrap().get(&[2]), Some(5));
+        buffer.release(py);
+        assert!(transposed.try_borrow_mut().is_ok());
+    });
+}
+
+#[derive(Clone, Copy)]
+struct Opaque(u32);
+
+unsafe impl pyo3::buffer::Element for Opaque {
+    fn is_compatible_format(format: &CStr) -> bool {
+        format.to_bytes() == b"I"
+    }
+}
+
+#[pyclass]
+struct ExportedOpaque {
+    data: Vec<Opaque>,
+}
+
+impl BufferExport for ExportedOpaque {
+    type Item = Opaque;
+
+    fn buffer(&self) -> &[Opaque] {
+        &self.data
+    }
+}
+
+#[test]
+fn test_buffer_export_without_layout() {
+    Python::with_gil(|py| {
+        let opaque = PyCell::new(
+            py,
+            ExportedOpaque {
+                data: vec![Opaque(1)],
+            },
+        )
+        .unwrap();
+        py_expect_exception!(py, opaque, "memoryview(opaque)", PyBufferError);
+        assert_eq!(opaque.borrow().data[0].0, 1);
+    });
+}
@@ -341,3 +341,118 @@ fn test_tuple_struct_class() {
         assert_eq!(instance.borrow(py).0, 1234);
     });
 }
+
+#[pyclass(eq)]
+#[derive(PartialEq)]
+struct EqClass {
+    value: i32,
+}
+
+#[pyclass(eq, ord, hash)]
+#[derive(PartialEq, PartialOrd, Hash)]
+struct OrdHashClass {
+    value: i32,
+}
+
+#[test]
+fn test_pyclass_eq() {
+    Python::with_gil(|py| {
+        let a = Py::new(py, EqClass { value: 1 }).unwrap();
+        let b = Py::new(py, EqClass { value: 1 }).unwrap();
+        let c = Py::new(py, EqClass { value: 2 }).unwrap();
+        py_assert!(py, a b c, "a == b and a != c and not (a != b) and not (a == c)");
+        py_assert!(py, a, "a != 1 and not (a == 1)");
+        py_assert!(py, a, "a.__hash__ is None");
+        py_expect_exception!(py, a, "hash(a)", PyTypeError);
+        py_expect_exception!(py, a b, "a < b", PyTypeError);
+    });
+}
+
+#[test]
+fn test_pyclass_ord_hash() {
+    Python::with_gil(|py| {
+        let a = Py::new(py, OrdHashClass { value: 1 }).unwrap();
+        let b = Py::new(py, OrdHashClass { value: 1 }).unwrap();
+        let c = Py::new(py, OrdHashClass { value: 2 }).unwrap();
+        py_assert!(py, a b c, "a == b and a <= b and a >= b and a < c and c > a");
+        py_assert!(py, a c, "not (a > c) and not (c <= a) and a != c");
+        py_assert!(py, a b c, "hash(a) == hash(b) and len({a, b, c}) == 2");
+        py_expect_exception!(py, a, "a < 1", PyTypeError);
+    });
+}
+
+#[pyclass(repr, get_all)]
+struct Point {
+    x: i32,
+    #[pyo3(name = "why")]
+    y: i32,
+    label: Option<String>,
+}
+
+#[pyclass(repr)]
+struct ReprOnlyReadable {
+    #[pyo3(get)]
+    visible: i32,
+    #[allow(dead_code)]
+    hidden: i32,
+}
+
+#[pyclass(repr)]
+struct ReprTuple(#[pyo3(get, name = "value")] f64);
+
+#[pyclass(repr, get_all)]
+struct ReprRecursive {
+    inner: Option<PyObject>,
+}
+
+#[test]
+fn test_pyclass_repr() {
+    Python::with_gil(|py| {
+        let point = Py::new(
+            py,
+            Point {
+                x: 1,
+                y: -2,
+                label: Some("a".to_owned()),
+            },
+        )
+        .unwrap();
+        py_assert!(
+            py,
+            point,
+            "repr(point) == \"Point(x=1, why=-2, label='a')\""
+        );
+
+        let readable = Py::new(
+            py,
+            ReprOnlyReadable {
+                visible: 3,
+                hidden: 4,
+            },
+        )
+        .unwrap();
+        py_assert!(
+            py,
+            readable,
+            "repr(readable) == 'ReprOnlyReadable(visible=3)'"
+        );
+
+        let tuple = Py::new(py, ReprTuple(0.5)).unwrap();
+        py_assert!(py, tuple, "repr(tuple) == 'ReprTuple(value=0.5)'");
+
+        let list = pyo3::types::PyList::empty(py);
+        let recursive = Py::new(
+            py,
+            ReprRecursive {
+                inner: Some(list.into()),
+            },
+        )
+        .unwrap();
+        list.append(&recursive).unwrap();
+        py_assert!(
+            py,
+            recursive,
+            "repr(recursive) == 'ReprRecursive(inner=[...])'"
+        );
+    });
+}
@@ -0,0 +1,212 @@
+use pyo3::prelude::*;
+use pyo3::py_run;
+use pyo3::types::IntoPyDict;
+
+mod common;
+
+#[pyclass(pickle, module = "test_class_pickle", get_all)]
+#[derive(Clone)]
+struct Point {
+    x: i64,
+    y: i64,
+    label: Option<String>,
+}
+
+#[pyclass(pickle, module = "test_class_pickle", subclass)]
+struct Polygon(Vec<Point>, #[pyo3(get, name = "closed")] bool);
+
+#[pymethods]
+impl Polygon {
+    #[new]
+    fn new(points: Vec<Point>, closed: bool) -> Self {
+        Polygon(points, closed)
+    }
+
+    fn num_points(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[pyclass(pickle, module = "test_class_pickle")]
+struct Marker;
+
+#[pyclass(extends = Polygon, subclass)]
+struct ColoredPolygon {
+    #[pyo3(get)]
+    color: String,
+}
+
+#[pymethods]
+impl ColoredPolygon {
+    #[new]
+    fn new(color: String) -> (Self, Polygon) {
+        (ColoredPolygon { color }, Polygon(Vec::new(), true))
+    }
+}
+
+/// Registers the classes in a module named after their `module` option, so that `pickle` can
+/// find them.
+fn add_pickle_module(py: Python) {
+    let module = PyModule::new(py, "test_class_pickle").unwrap();
+    module.add_class::<Point>().unwrap();
+    module.add_class::<Polygon>().unwrap();
+    module.add_class::<Marker>().unwrap();
+    py.import("sys")
+        .unwrap()
+        .getattr("modules")
+        .unwrap()
+        .set_item("test_class_pickle", module)
+        .unwrap();
+}
+
+#[test]
+fn test_pickle_fields() {
+    Python::with_gil(|py| {
+        add_pickle_module(py);
+        py.run(
+            r#"
+import pickle
+from test_class_pickle import Marker, Point, Polygon
+
+p = Point._pyo3_unpickle((1, 2, None))
+p2 = pickle.loads(pickle.dumps(p))
+assert type(p2) is Point
+assert (p2.x, p2.y, p2.label) == (1, 2, None)
+
+poly = Polygon([p, Point._pyo3_unpickle((3, 4, "corner"))], True)
+poly2 = pickle.loads(pickle.dumps(poly, protocol=pickle.HIGHEST_PROTOCOL))
+assert poly2.num_points() == 2 and poly2.closed
+points, closed = poly2.__reduce__()[1][0]
+assert [(q.x, q.y, q.label) for q in points] == [(1, 2, None), (3, 4, "corner")]
+
+assert type(pickle.loads(pickle.dumps(Marker._pyo3_unpickle(())))) is Marker
+"#,
+            None,
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_reduce_and_setstate() {
+    Python::with_gil(|py| {
+        add_pickle_module(py);
+        let point = Py::new(
+            py,
+            Point {
+                x: 1,
+                y: 2,
+                label: Some("a".to_string()),
+            },
+        )
+        .unwrap();
+        py_run!(
+            py,
+            point,
+            r#"
+from test_class_pickle import Point
+
+func, args = point.__reduce__()
+assert func == Point._pyo3_unpickle
+assert args == ((1, 2, "a"),)
+
+point.__setstate__((5, 6, None))
+assert (point.x, point.y, point.label) == (5, 6, None)
+
+for state in [(5, 6), [5, 6, None]]:
+    try:
+        point.__setstate__(state)
+    except (ValueError, TypeError):
+        pass
+    else:
+        assert False, state
+try:
+    point.__setstate__((5, "six", None))
+except TypeError:
+    pass
+else:
+    assert False
+assert (point.x, point.y) == (5, 6)
+"#
+        );
+        assert_eq!(point.borrow(py).x, 5);
+        assert_eq!(point.borrow(py).label, None);
+    });
+}
+
+#[test]
+fn test_copy_python_subclass() {
+    Python::with_gil(|py| {
+        add_pickle_module(py);
+        py.run(
+            r#"
+import copy
+from test_class_pickle import Polygon
+
+class Square(Polygon):
+    pass
+
+square = Square([], False)
+square.color = "red"
+square2 = copy.deepcopy(square)
+assert type(square2) is Square
+assert square2 is not square and not square2.closed
+assert square2.color == "red"
+
+func, args, state = square.__reduce__()
+assert state == {"color": "red"}
+assert Square([], False).__reduce__()[2:] == ()
+"#,
+            None,
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_unpickle_rust_subclass() {
+    Python::with_gil(|py| {
+        add_pickle_module(py);
+        let colored = py.get_type::<ColoredPolygon>();
+        let globals = [("ColoredPolygon", colored)].into_py_dict(py);
+        py.run(
+            r#"
+import copy
+
+class Shape(ColoredPolygon):
+    pass
+
+# unpickling only creates the data of `Polygon`, not that of `ColoredPolygon`
+for cls in (ColoredPolygon, Shape):
+    try:
+        cls._pyo3_unpickle(([], True))
+    except TypeError as e:
+        assert "ColoredPolygon has Rust data of its own" in str(e), e
+    else:
+        assert False, "unpickled a Rust subclass"
+    try:
+        copy.copy(cls("red"))
+    except TypeError:
+        pass
+    else:
+        assert False, "copied a Rust subclass"
+"#,
+            Some(globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[test]
+fn test_pickle_borrowed_mutably() {
+    Python::with_gil(|py| {
+        add_pickle_module(py);
+        let marker = PyCell::new(py, Marker).unwrap();
+        let _guard = marker.borrow_mut();
+        py_expect_exception!(py, marker, "marker.__reduce__()", PyRuntimeError);
+        py_expect_exception!(py, marker, "marker.__setstate__(())", PyRuntimeError);
+    });
+}
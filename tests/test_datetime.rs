@@ -1,6 +1,7 @@
 #![cfg(not(Py_LIMITED_API))]
 
 use pyo3::prelude::*;
+use pyo3::py_run;
 use pyo3::types::IntoPyDict;
 
 fn _get_subclasses<'p>(
@@ -196,3 +197,88 @@ fn test_pydatetime_out_of_bounds() {
         dt.unwrap_err();
     }
 }
+
+#[test]
+fn test_pytzinfo_utc_and_timezone() {
+    use pyo3::types::{PyDelta, PyTzInfo};
+
+    Python::with_gil(|py| {
+        let utc = PyTzInfo::utc(py).unwrap();
+        py_run!(
+            py,
+            utc,
+            "import datetime; assert utc is datetime.timezone.utc"
+        );
+
+        let offset = PyDelta::new(py, 0, -5 * 3600, 0, true).unwrap();
+        let tz = PyTzInfo::timezone(py, offset).unwrap();
+        py_run!(
+            py,
+            tz,
+            "import datetime; assert tz == datetime.timezone(datetime.timedelta(hours=-5))"
+        );
+
+        let offset = PyDelta::new(py, 1, 0, 0, true).unwrap();
+        assert!(PyTzInfo::timezone(py, offset).is_err());
+    });
+}
+
+#[pyclass(extends=pyo3::types::PyTzInfo)]
+struct HalfHourEast {
+    name: &'static str,
+}
+
+#[pymethods]
+impl HalfHourEast {
+    #[new]
+    fn new() -> Self {
+        HalfHourEast { name: "+00:30" }
+    }
+}
+
+impl pyo3::types::TzInfo for HalfHourEast {
+    fn utcoffset<'p>(
+        &self,
+        py: Python<'p>,
+        _dt: Option<&'p pyo3::types::PyDateTime>,
+    ) -> PyResult<Option<&'p pyo3::types::PyDelta>> {
+        pyo3::types::PyDelta::new(py, 0, 1800, 0, true).map(Some)
+    }
+
+    fn tzname(
+        &self,
+        _py: Python,
+        _dt: Option<&pyo3::types::PyDateTime>,
+    ) -> PyResult<Option<String>> {
+        Ok(Some(self.name.to_owned()))
+    }
+}
+
+#[test]
+fn test_tzinfo_subclass() {
+    Python::with_gil(|py| {
+        let tz = PyCell::new(py, HalfHourEast::new()).unwrap();
+        py_run!(
+            py,
+            tz,
+            r#"
+import datetime
+assert isinstance(tz, datetime.tzinfo)
+dt = datetime.datetime(2021, 8, 9, 12, 0, tzinfo=tz)
+assert dt.utcoffset() == datetime.timedelta(minutes=30)
+assert dt.dst() == datetime.timedelta(0)
+assert dt.tzname() == "+00:30"
+assert dt.isoformat() == "2021-08-09T12:00:00+00:30"
+utc = datetime.datetime(2021, 8, 9, 11, 30, tzinfo=datetime.timezone.utc)
+assert utc.astimezone(tz) == dt
+assert utc.astimezone(tz).hour == 12
+try:
+    tz.utcoffset(1)
+except TypeError:
+    pass
+else:
+    assert False, "expected TypeError"
+"#
+        );
+    });
+}
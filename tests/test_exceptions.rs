@@ -92,3 +92,164 @@ fn test_exception_nosegfault() {
     assert!(io_err().is_err());
     assert!(parse_int().is_err());
 }
+
+#[cfg(not(Py_LIMITED_API))]
+mod pyclass_exceptions {
+    use pyo3::class::PyObjectProtocol;
+    use pyo3::exceptions::{PyException, PyTypeError, PyValueError};
+    use pyo3::prelude::*;
+    use pyo3::types::{IntoPyDict, PyDict};
+    use pyo3::{py_run, PyClassInitializer};
+
+    #[pyclass(extends=PyException, module="test_exceptions_module")]
+    struct ParseError {
+        #[pyo3(get)]
+        message: String,
+        #[pyo3(get)]
+        line: usize,
+    }
+
+    #[pymethods]
+    impl ParseError {
+        #[new]
+        fn new(py: Python, message: String, line: usize) -> PyClassInitializer<Self> {
+            PyClassInitializer::from(ParseError {
+                message: message.clone(),
+                line,
+            })
+            .with_base_args(py, (message, line))
+        }
+    }
+
+    #[pyproto]
+    impl PyObjectProtocol for ParseError {
+        fn __str__(&self) -> String {
+            format!("{} at line {}", self.message, self.line)
+        }
+    }
+
+    #[pyclass(extends=PyValueError)]
+    struct OutOfRange {
+        value: i64,
+    }
+
+    #[pyfunction]
+    fn parse(py: Python, input: &str) -> PyResult<i64> {
+        match input.find('!') {
+            Some(pos) => Err(ParseError::new(py, "unexpected '!'".to_string(), pos + 1).into()),
+            None => Ok(input.len() as i64),
+        }
+    }
+
+    #[test]
+    fn test_raise_from_rust() {
+        Python::with_gil(|py| {
+            let parse = wrap_pyfunction!(parse)(py).unwrap();
+            let cls = py.get_type::<ParseError>();
+            py_run!(
+                py,
+                parse cls,
+                r#"
+                assert parse("ok") == 2
+                try:
+                    parse("hi!")
+                except cls as e:
+                    assert isinstance(e, Exception)
+                    assert e.line == 3
+                    assert e.args == ("unexpected '!'", 3)
+                    assert str(e) == "unexpected '!' at line 3"
+                else:
+                    assert False
+                "#
+            );
+
+            let err = parse.call1(("!",)).unwrap_err();
+            assert_eq!(err.to_string(), "ParseError: unexpected '!' at line 1");
+            let payload = err.instance_of::<ParseError>(py).unwrap().borrow();
+            assert_eq!(payload.message, "unexpected '!'");
+            assert_eq!(payload.line, 1);
+            assert!(err.instance_of::<OutOfRange>(py).is_none());
+        });
+    }
+
+    #[test]
+    fn test_extract_from_python_exception() {
+        Python::with_gil(|py| {
+            let cls = py.get_type::<ParseError>();
+            let locals = [("cls", cls)].into_py_dict(py);
+            let err = py
+                .run("raise cls('bad input', 7)", None, Some(locals))
+                .unwrap_err();
+            assert!(err.matches(py, cls));
+            assert_eq!(err.instance_of::<ParseError>(py).unwrap().borrow().line, 7);
+        });
+    }
+
+    #[test]
+    fn test_native_exception_base() {
+        Python::with_gil(|py| {
+            let err = PyErr::from(
+                PyClassInitializer::from(OutOfRange { value: 300 })
+                    .with_base_args(py, ("too big",)),
+            );
+            assert!(err.is_instance::<PyValueError>(py));
+            assert_eq!(err.to_string(), "OutOfRange: too big");
+            assert_eq!(
+                err.instance_of::<OutOfRange>(py).unwrap().borrow().value,
+                300
+            );
+
+            // without arguments, the default `__str__` is empty
+            let err = PyErr::from(PyClassInitializer::from(OutOfRange { value: 0 }));
+            assert_eq!(err.to_string(), "OutOfRange: ");
+        });
+    }
+
+    #[pyclass]
+    struct NotAnException {}
+
+    #[test]
+    fn test_not_an_exception() {
+        Python::with_gil(|py| {
+            let err = PyErr::from(PyClassInitializer::from(NotAnException {}));
+            assert!(err.is_instance::<PyTypeError>(py));
+            assert_eq!(
+                err.to_string(),
+                "TypeError: exceptions must derive from BaseException"
+            );
+        });
+    }
+
+    #[test]
+    fn test_pickle() {
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "test_exceptions_module").unwrap();
+            module.add_class::<ParseError>().unwrap();
+            py.import("sys")
+                .unwrap()
+                .dict()
+                .get_item("modules")
+                .unwrap()
+                .downcast::<PyDict>()
+                .unwrap()
+                .set_item("test_exceptions_module", module)
+                .unwrap();
+
+            let err = PyErr::from(ParseError::new(py, "unexpected EOF".to_string(), 12));
+            let inst = err.instance(py);
+            py_run!(
+                py,
+                inst,
+                r#"
+                import pickle
+                from test_exceptions_module import ParseError
+                copy = pickle.loads(pickle.dumps(inst))
+                assert type(copy) is ParseError
+                assert copy.args == ("unexpected EOF", 12)
+                assert (copy.message, copy.line) == ("unexpected EOF", 12)
+                assert str(copy) == "unexpected EOF at line 12"
+                "#
+            );
+        });
+    }
+}
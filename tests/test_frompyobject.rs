@@ -215,8 +215,8 @@ fn test_struct_nested_type_errors() {
         assert!(test.is_err());
         assert_eq!(
             extract_traceback(py,test.unwrap_err()),
-            "TypeError: failed to extract field Baz.tup: TypeError: failed to extract field Tuple.1: \
-         TypeError: \'str\' object cannot be interpreted as an integer"
+            "ExtractionError: failed to extract field Baz.tup.1: 'str' object cannot be \
+             interpreted as an integer: TypeError: 'str' object cannot be interpreted as an integer"
         );
     });
 }
@@ -237,8 +237,8 @@ fn test_struct_nested_type_errors_with_generics() {
         assert!(test.is_err());
         assert_eq!(
             extract_traceback(py, test.unwrap_err()),
-            "TypeError: failed to extract field Baz.e: TypeError: failed to extract field E.test: \
-         TypeError: \'str\' object cannot be interpreted as an integer",
+            "ExtractionError: failed to extract field Baz.e.test: 'str' object cannot be \
+             interpreted as an integer: TypeError: 'str' object cannot be interpreted as an integer",
         );
     });
 }
@@ -250,9 +250,9 @@ fn test_transparent_struct_error_message() {
         let tup = B::extract(tup.as_ref(py));
         assert!(tup.is_err());
         assert_eq!(
-            extract_traceback(py,tup.unwrap_err()),
-            "TypeError: failed to extract field B.test: TypeError: \'int\' object cannot be converted \
-         to \'PyString\'"
+            extract_traceback(py, tup.unwrap_err()),
+            "ExtractionError: failed to extract field B.test: 'int' object cannot be converted to \
+             'PyString': TypeError: 'int' object cannot be converted to 'PyString'"
         );
     });
 }
@@ -265,8 +265,8 @@ fn test_tuple_struct_error_message() {
         assert!(tup.is_err());
         assert_eq!(
             extract_traceback(py, tup.unwrap_err()),
-            "TypeError: failed to extract field Tuple.0: TypeError: \'int\' object cannot be \
-         converted to \'PyString\'"
+            "ExtractionError: failed to extract field Tuple.0: 'int' object cannot be converted to \
+             'PyString': TypeError: 'int' object cannot be converted to 'PyString'"
         );
     });
 }
@@ -409,7 +409,7 @@ fn test_err_rename() {
         assert!(f.is_err());
         assert_eq!(
             f.unwrap_err().to_string(),
-            "TypeError: failed to extract enum Bar (\'Union[str, uint, int]\')\n- variant A (str): \
+            "ExtractionError: failed to extract enum Bar (\'Union[str, uint, int]\')\n- variant A (str): \
          \'dict\' object cannot be converted to \'PyString\'\n- variant B (uint): \'dict\' object \
          cannot be interpreted as an integer\n- variant C (int): \'dict\' object cannot be \
          interpreted as an integer\n"
@@ -417,6 +417,46 @@ fn test_err_rename() {
     });
 }
 
+#[derive(Debug, FromPyObject)]
+pub enum Shape {
+    Circle {
+        #[pyo3(item)]
+        radius: f64,
+    },
+    Rect(f64, f64),
+}
+
+#[test]
+fn test_enum_variant_paths() {
+    Python::with_gil(|py| {
+        let dict = py.eval("{'radius': 'big'}", None, None).unwrap();
+        let err = Shape::extract(dict).unwrap_err();
+        let extraction_error: &pyo3::ExtractionError = err.instance(py).downcast().unwrap();
+        assert_eq!(
+            extraction_error.type_name().unwrap().as_deref(),
+            Some("Shape")
+        );
+        assert!(extraction_error.path().unwrap().is_empty());
+
+        let variant_errors = extraction_error.variant_errors().unwrap();
+        assert_eq!(variant_errors.len(), 2);
+        let circle: &pyo3::ExtractionError = variant_errors[0].instance(py).downcast().unwrap();
+        assert_eq!(
+            circle.type_name().unwrap().as_deref(),
+            Some("Shape::Circle")
+        );
+        assert_eq!(
+            circle.path().unwrap(),
+            vec![pyo3::PathSegment::Field("radius".to_owned())]
+        );
+        assert!(!variant_errors[1].is_instance::<pyo3::ExtractionError>(py));
+
+        let err = err.into_py(py);
+        py_assert!(py, err, "err.path == ()");
+        py_assert!(py, err, "err.variant_errors[0].path == ('radius',)");
+    });
+}
+
 #[derive(Debug, FromPyObject)]
 pub struct Zap {
     #[pyo3(item)]
@@ -443,3 +483,186 @@ fn test_from_py_with() {
         assert_eq!(zap.some_object_length, 3usize);
     });
 }
+
+#[derive(Debug, FromPyObject)]
+pub struct WithDefaults {
+    #[pyo3(item)]
+    required: String,
+    #[pyo3(item, default)]
+    count: usize,
+    #[pyo3(item, default = "\"localhost\".to_owned()")]
+    host: String,
+    #[pyo3(item)]
+    comment: Option<String>,
+}
+
+#[test]
+fn test_field_defaults() {
+    Python::with_gil(|py| {
+        let dict = py.eval(r#"{"required": "yes"}"#, None, None).unwrap();
+        let with_defaults = WithDefaults::extract(dict).unwrap();
+        assert_eq!(with_defaults.required, "yes");
+        assert_eq!(with_defaults.count, 0);
+        assert_eq!(with_defaults.host, "localhost");
+        assert_eq!(with_defaults.comment, None);
+
+        let dict = py
+            .eval(
+                r#"{"required": "yes", "count": 3, "host": "example.com", "comment": "hi"}"#,
+                None,
+                None,
+            )
+            .unwrap();
+        let with_defaults = WithDefaults::extract(dict).unwrap();
+        assert_eq!(with_defaults.count, 3);
+        assert_eq!(with_defaults.host, "example.com");
+        assert_eq!(with_defaults.comment.as_deref(), Some("hi"));
+
+        // Missing required keys and present values of the wrong type are still errors
+        let dict = py.eval(r#"{"count": 3}"#, None, None).unwrap();
+        let err = WithDefaults::extract(dict).unwrap_err();
+        assert!(err.is_instance::<pyo3::exceptions::PyKeyError>(py));
+        let dict = py
+            .eval(r#"{"required": "yes", "count": "3"}"#, None, None)
+            .unwrap();
+        assert_eq!(
+            extract_traceback(py, WithDefaults::extract(dict).unwrap_err()),
+            "ExtractionError: failed to extract field WithDefaults.count: 'str' object cannot \
+             be interpreted as an integer: TypeError: 'str' object cannot be interpreted as an \
+             integer"
+        );
+    });
+}
+
+#[derive(Debug, FromPyObject)]
+pub struct OptionalAttribute {
+    value: Option<i32>,
+}
+
+#[test]
+fn test_optional_attribute() {
+    Python::with_gil(|py| {
+        let obj = py.eval("object()", None, None).unwrap();
+        assert_eq!(OptionalAttribute::extract(obj).unwrap().value, None);
+
+        let obj = py
+            .eval("type('A', (), {'value': 'foo'})()", None, None)
+            .unwrap();
+        assert!(OptionalAttribute::extract(obj).is_err());
+    });
+}
+
+#[derive(Debug, FromPyObject)]
+#[pyo3(from_item_all, rename_all = "camelCase")]
+pub struct ServerConfig {
+    server_name: String,
+    listen_port: u16,
+    #[pyo3(item("TLS"), default)]
+    tls: bool,
+    #[pyo3(attribute("real"))]
+    real_part: f64,
+}
+
+#[derive(Debug, FromPyObject)]
+#[pyo3(rename_all = "SCREAMING-KEBAB-CASE")]
+pub struct RenamedAttributes {
+    real: f64,
+    #[pyo3(attribute("imag"))]
+    imaginary_part: f64,
+}
+
+#[test]
+fn test_from_item_all_and_rename_all() {
+    Python::with_gil(|py| {
+        let dict = py
+            .eval(r#"{"serverName": "a", "listenPort": 80}"#, None, None)
+            .unwrap();
+        let err = ServerConfig::extract(dict).unwrap_err();
+        assert!(err.is_instance::<pyo3::exceptions::PyAttributeError>(py));
+
+        let locals = pyo3::types::PyDict::new(py);
+        py.run(
+            r#"
+class Config(dict):
+    real = 1.5
+
+config = Config(serverName="a", listenPort=80)
+"#,
+            None,
+            Some(locals),
+        )
+        .unwrap();
+        let config = ServerConfig::extract(locals.get_item("config").unwrap()).unwrap();
+        assert_eq!(config.server_name, "a");
+        assert_eq!(config.listen_port, 80);
+        assert!(!config.tls);
+        assert_eq!(config.real_part, 1.5);
+
+        let obj = py.eval("2+3j", None, None).unwrap();
+        let err = RenamedAttributes::extract(obj).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "AttributeError: 'complex' object has no attribute 'REAL'"
+        );
+    });
+}
+
+#[derive(Debug, FromPyObject)]
+pub struct Server {
+    #[pyo3(item)]
+    port: u16,
+}
+
+#[derive(Debug, FromPyObject)]
+pub struct Config {
+    #[pyo3(item)]
+    servers: Vec<Server>,
+}
+
+#[test]
+fn test_nested_field_path() {
+    Python::with_gil(|py| {
+        let dict = py
+            .eval(
+                r#"{"servers": [{"port": 1}, {"port": 2}, {"port": 3}, {"port": "4"}]}"#,
+                None,
+                None,
+            )
+            .unwrap();
+        let err = Config::extract(dict).unwrap_err();
+        assert!(err.is_instance::<pyo3::exceptions::PyTypeError>(py));
+        assert_eq!(
+            err.instance(py).str().unwrap().to_str().unwrap(),
+            "failed to extract field Config.servers[3].port: 'str' object cannot be interpreted \
+             as an integer"
+        );
+
+        let extraction_error: &pyo3::ExtractionError = err.instance(py).downcast().unwrap();
+        assert_eq!(
+            extraction_error.type_name().unwrap().as_deref(),
+            Some("Config")
+        );
+        assert_eq!(
+            extraction_error.path().unwrap(),
+            vec![
+                pyo3::PathSegment::Field("servers".to_owned()),
+                pyo3::PathSegment::Index(3),
+                pyo3::PathSegment::Field("port".to_owned()),
+            ]
+        );
+        assert_eq!(
+            err.cause(py).unwrap().to_string(),
+            "TypeError: 'str' object cannot be interpreted as an integer"
+        );
+
+        let err = err.into_py(py);
+        py_assert!(py, err, "err.path == ('servers', 3, 'port')");
+
+        // Errors of sequences of plain values are left unchanged
+        let list = py.eval("[1, 'a']", None, None).unwrap();
+        assert_eq!(
+            list.extract::<Vec<u16>>().unwrap_err().to_string(),
+            "TypeError: 'str' object cannot be interpreted as an integer"
+        );
+    });
+}
@@ -160,3 +160,54 @@ fn tuple_struct_getter_setter() {
     py_run!(py, inst, "inst.num = 20");
     py_assert!(py, inst, "inst.num == 20");
 }
+
+#[pyclass(get_all, set_all)]
+struct All {
+    num: i32,
+    #[pyo3(name = "text")]
+    string: String,
+}
+
+#[test]
+fn get_all_and_set_all() {
+    Python::with_gil(|py| {
+        let inst = Py::new(
+            py,
+            All {
+                num: 10,
+                string: "hello".to_owned(),
+            },
+        )
+        .unwrap();
+
+        py_assert!(py, inst, "inst.num == 10 and inst.text == 'hello'");
+        py_run!(py, inst, "inst.num = 20; inst.text = 'world'");
+        py_assert!(py, inst, "inst.num == 20 and inst.text == 'world'");
+        assert_eq!(inst.borrow(py).string, "world");
+    });
+}
+
+#[pyclass(get_all)]
+struct ReadOnlyAll {
+    num: i32,
+    #[pyo3(set)]
+    settable: i32,
+}
+
+#[test]
+fn get_all_with_field_set() {
+    Python::with_gil(|py| {
+        let inst = Py::new(
+            py,
+            ReadOnlyAll {
+                num: 1,
+                settable: 2,
+            },
+        )
+        .unwrap();
+
+        py_assert!(py, inst, "inst.num == 1 and inst.settable == 2");
+        py_run!(py, inst, "inst.settable = 3; assert inst.settable == 3");
+        py_expect_exception!(py, inst, "inst.num = 5", PyAttributeError);
+    });
+}
@@ -154,7 +154,10 @@ except Exception as e:
 mod inheriting_native_type {
     use super::*;
     use pyo3::exceptions::PyException;
-    use pyo3::types::{IntoPyDict, PyDict, PySet};
+    use pyo3::types::{
+        IntoPyDict, PyByteArray, PyBytes, PyDict, PyFloat, PyList, PyLong, PySet, PyString, PyTuple,
+    };
+    use pyo3::PyClassInitializer;
 
     #[pyclass(extends=PySet)]
     #[derive(Debug)]
@@ -269,6 +272,347 @@ mod inheriting_native_type {
             )
         })
     }
+
+    #[test]
+    fn native_layout_sizes() {
+        use pyo3::ffi;
+        use std::mem::size_of;
+        unsafe {
+            assert_eq!(
+                ffi::PyList_Type.tp_basicsize as usize,
+                size_of::<ffi::PyListObject>()
+            );
+            assert_eq!(
+                ffi::PyByteArray_Type.tp_basicsize as usize,
+                size_of::<ffi::PyByteArrayObject>()
+            );
+            assert_eq!(
+                ffi::PyUnicode_Type.tp_basicsize as usize,
+                size_of::<ffi::PyUnicodeObject>()
+            );
+            assert_eq!(
+                ffi::PyType_Type.tp_basicsize as usize,
+                size_of::<ffi::PyHeapTypeObject>()
+            );
+        }
+    }
+
+    #[pyclass(extends=PyList)]
+    struct TrackedList {
+        #[pyo3(get)]
+        pushed: usize,
+    }
+
+    #[pymethods]
+    impl TrackedList {
+        // `list.__init__` receives the same arguments and fills the list
+        #[new]
+        #[args(_iterable = "None")]
+        fn new(_iterable: Option<&PyAny>) -> Self {
+            TrackedList { pushed: 0 }
+        }
+
+        fn push(slf: &PyCell<Self>, item: PyObject) -> PyResult<()> {
+            slf.downcast::<PyList>()?.append(item)?;
+            slf.borrow_mut().pushed += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn inherit_list() {
+        Python::with_gil(|py| {
+            let cls = py.get_type::<TrackedList>();
+            py_run!(
+                py,
+                cls,
+                r#"
+                l = cls([5, 4])
+                assert isinstance(l, list)
+                assert l == [5, 4]
+                for i in (3, 1, 2):
+                    l.push(i)
+                l.extend(range(10, 1000))
+                l.sort(reverse=True)
+                assert l[:3] == [999, 998, 997]
+                assert len(l) == 995
+                del l[:]
+                assert l == [] and l.pushed == 3
+                "#
+            );
+
+            let list = PyCell::new(py, TrackedList::new(None)).unwrap();
+            list.downcast::<PyList>().unwrap().append(1).unwrap();
+            assert_eq!(list.len().unwrap(), 1);
+            assert_eq!(list.borrow().pushed, 0);
+        });
+    }
+
+    #[pyclass(extends=PyByteArray)]
+    struct Packet {
+        #[pyo3(get)]
+        channel: u8,
+    }
+
+    #[pymethods]
+    impl Packet {
+        #[new]
+        fn new(channel: u8) -> Self {
+            Packet { channel }
+        }
+    }
+
+    #[test]
+    fn inherit_bytearray() {
+        Python::with_gil(|py| {
+            let packet = PyCell::new(py, Packet::new(7)).unwrap();
+            py_run!(
+                py,
+                packet,
+                r#"
+                assert isinstance(packet, bytearray)
+                packet.extend(b"payload" * 100)
+                packet[:7] = b"PAYLOAD"
+                assert bytes(packet[:10]) == b"PAYLOADpay"
+                assert len(packet) == 700
+                assert packet.channel == 7
+                "#
+            );
+        });
+    }
+
+    #[pyclass(extends=PyString)]
+    struct Symbol {
+        #[pyo3(get)]
+        id: u32,
+    }
+
+    #[pymethods]
+    impl Symbol {
+        #[new]
+        fn new(py: Python, name: &str, id: u32) -> PyClassInitializer<Self> {
+            PyClassInitializer::from(Symbol { id }).with_base_args(py, (name,))
+        }
+    }
+
+    #[test]
+    fn inherit_str() {
+        Python::with_gil(|py| {
+            let cls = py.get_type::<Symbol>();
+            py_run!(
+                py,
+                cls,
+                r#"
+                s = cls("größe", 42)
+                assert isinstance(s, str)
+                assert s == "größe" and hash(s) == hash("größe")
+                assert s.upper() == "GRÖSSE"
+                assert {s: 1}["größe"] == 1
+                assert s.id == 42
+                "#
+            );
+
+            let symbol = Py::new(py, Symbol::new(py, "x", 1)).unwrap();
+            assert_eq!(symbol.as_ref(py).str().unwrap().to_str().unwrap(), "x");
+            assert_eq!(symbol.borrow(py).id, 1);
+        });
+    }
+
+    #[pyclass(extends=PyTuple, subclass)]
+    struct LabeledTuple {
+        #[pyo3(get)]
+        label: String,
+        #[pyo3(get)]
+        tag: PyObject,
+    }
+
+    #[pymethods]
+    impl LabeledTuple {
+        #[new]
+        fn new(py: Python, items: &PyAny, label: String) -> PyClassInitializer<Self> {
+            let tag = py.None();
+            PyClassInitializer::from(LabeledTuple { label, tag }).with_base_args(py, (items,))
+        }
+
+        fn relabel(&mut self, label: String) {
+            self.label = label;
+        }
+    }
+
+    #[test]
+    fn inherit_tuple() {
+        Python::with_gil(|py| {
+            let cls = py.get_type::<LabeledTuple>();
+            py_run!(
+                py,
+                cls,
+                r#"
+                for n in (0, 1, 2, 3, 7, 100):
+                    t = cls(range(n), f"range {n}")
+                    assert isinstance(t, tuple)
+                    assert t == tuple(range(n)) and len(t) == n
+                    assert t.label == f"range {n}"
+                    t.relabel("changed")
+                    assert t.label == "changed"
+                    assert t[::-1] == tuple(reversed(range(n)))
+
+                class PySub(cls):
+                    pass
+
+                sub = PySub("abc", "py")
+                sub.extra = [1]
+                assert sub == ("a", "b", "c")
+                assert (sub.label, sub.extra) == ("py", [1])
+                "#
+            );
+
+            // the Rust contents are dropped with the tuple
+            let tag = py.eval("object()", None, None).unwrap();
+            let items = vec![1, 2, 3, 4, 5].to_object(py);
+            let tuple = Py::new(
+                py,
+                PyClassInitializer::from(LabeledTuple {
+                    label: "tagged".into(),
+                    tag: tag.into(),
+                })
+                .with_base_args(py, (items,)),
+            )
+            .unwrap();
+            assert_eq!(tuple.as_ref(py).len().unwrap(), 5);
+            assert_eq!(tag.get_refcnt(), 2);
+            drop(tuple);
+            assert_eq!(tag.get_refcnt(), 1);
+        });
+    }
+
+    #[pyclass(extends=LabeledTuple)]
+    struct CountedTuple {
+        #[pyo3(get)]
+        count: usize,
+    }
+
+    #[pymethods]
+    impl CountedTuple {
+        #[new]
+        fn new(py: Python, items: &PyAny) -> PyResult<PyClassInitializer<Self>> {
+            let count = items.len()?;
+            Ok(LabeledTuple::new(py, items, "counted".into()).add_subclass(CountedTuple { count }))
+        }
+    }
+
+    #[test]
+    fn inherit_tuple_subclass() {
+        Python::with_gil(|py| {
+            let cls = py.get_type::<CountedTuple>();
+            py_run!(
+                py,
+                cls,
+                r#"
+                t = cls([1.5, None, "x"])
+                assert t == (1.5, None, "x")
+                assert (t.count, t.label) == (3, "counted")
+                t.relabel("again")
+                assert (t.count, t.label) == (3, "again")
+                "#
+            );
+        });
+    }
+
+    #[pyclass(extends=PyBytes)]
+    struct Blob {
+        #[pyo3(get)]
+        checksum: u64,
+    }
+
+    #[pymethods]
+    impl Blob {
+        #[new]
+        fn new(py: Python, data: &[u8]) -> PyClassInitializer<Self> {
+            let checksum = data.iter().map(|&b| u64::from(b)).sum();
+            PyClassInitializer::from(Blob { checksum }).with_base_args(py, (data,))
+        }
+    }
+
+    #[test]
+    fn inherit_bytes() {
+        Python::with_gil(|py| {
+            let cls = py.get_type::<Blob>();
+            py_run!(
+                py,
+                cls,
+                r#"
+                for n in range(20):
+                    b = cls(bytes(range(n)))
+                    assert isinstance(b, bytes)
+                    assert b == bytes(range(n))
+                    assert b.checksum == sum(range(n))
+                "#
+            );
+        });
+    }
+
+    #[pyclass(extends=PyLong)]
+    struct Flag {
+        #[pyo3(get)]
+        name: String,
+    }
+
+    #[pymethods]
+    impl Flag {
+        #[new]
+        fn new(py: Python, value: &PyAny, name: String) -> PyClassInitializer<Self> {
+            PyClassInitializer::from(Flag { name }).with_base_args(py, (value,))
+        }
+    }
+
+    #[test]
+    fn inherit_int() {
+        Python::with_gil(|py| {
+            let cls = py.get_type::<Flag>();
+            py_run!(
+                py,
+                cls,
+                r#"
+                for value in (0, 1, -1, 2**40, -(2**100), 10**50):
+                    f = cls(value, "flag")
+                    assert isinstance(f, int)
+                    assert f == value and f + 1 == value + 1
+                    assert f.name == "flag"
+                "#
+            );
+        });
+    }
+
+    #[pyclass(extends=PyFloat)]
+    struct Measurement {
+        #[pyo3(get)]
+        unit: &'static str,
+    }
+
+    #[test]
+    fn inherit_float() {
+        Python::with_gil(|py| {
+            let init =
+                PyClassInitializer::from(Measurement { unit: "m" }).with_base_args(py, (2.5,));
+            let length = PyCell::new(py, init).unwrap();
+            py_run!(
+                py,
+                length,
+                "assert isinstance(length, float) and length * 2 == 5.0 and length.unit == 'm'"
+            );
+        });
+    }
+
+    #[pyclass(extends=PyTuple, dict)]
+    struct TupleWithDict {}
+
+    #[test]
+    #[should_panic(expected = "An error occurred while initializing class TupleWithDict")]
+    fn inherit_tuple_with_dict() {
+        Python::with_gil(|py| {
+            py.get_type::<TupleWithDict>();
+        });
+    }
 }
 
 #[pyclass(subclass)]
@@ -0,0 +1,181 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+
+#[macro_use]
+mod common;
+
+#[derive(Debug, Clone, PartialEq, FromPyObject, IntoPyObject, ToPyObject)]
+pub struct Point {
+    #[pyo3(item)]
+    x: i32,
+    #[pyo3(item("y_coord"))]
+    y: i32,
+}
+
+#[test]
+fn test_named_fields_struct() {
+    Python::with_gil(|py| {
+        let point = Point { x: 1, y: 2 };
+        let obj = point.to_object(py);
+        py_assert!(py, obj, "obj == {'x': 1, 'y_coord': 2}");
+
+        let obj = point.clone().into_py(py);
+        assert!(obj.as_ref(py).is_instance::<PyDict>().unwrap());
+        let roundtrip: Point = obj.extract(py).unwrap();
+        assert_eq!(roundtrip, point);
+    });
+}
+
+#[derive(Debug, IntoPyObject)]
+pub struct AttributeNames {
+    #[pyo3(attribute("renamed"))]
+    a: String,
+    b: Vec<u8>,
+}
+
+#[test]
+fn test_attribute_names_as_keys() {
+    Python::with_gil(|py| {
+        let obj = AttributeNames {
+            a: "foo".to_owned(),
+            b: vec![1, 2],
+        }
+        .into_py(py);
+        py_assert!(py, obj, "obj == {'renamed': 'foo', 'b': [1, 2]}");
+    });
+}
+
+#[derive(Debug, PartialEq, FromPyObject, IntoPyObject, ToPyObject)]
+pub struct Tuple(String, usize);
+
+#[test]
+fn test_tuple_struct() {
+    Python::with_gil(|py| {
+        let tuple = Tuple("test".to_owned(), 1);
+        let obj = tuple.to_object(py);
+        assert!(obj.as_ref(py).is_instance::<PyTuple>().unwrap());
+        py_assert!(py, obj, "obj == ('test', 1)");
+        let roundtrip: Tuple = tuple.into_py(py).extract(py).unwrap();
+        assert_eq!(roundtrip, Tuple("test".to_owned(), 1));
+    });
+}
+
+#[derive(Debug, PartialEq, FromPyObject, IntoPyObject)]
+pub struct TransparentTuple(String);
+
+#[derive(Debug, PartialEq, FromPyObject, IntoPyObject)]
+#[pyo3(transparent)]
+pub struct TransparentStruct {
+    inner: f64,
+}
+
+#[test]
+fn test_transparent() {
+    Python::with_gil(|py| {
+        let obj = TransparentTuple("test".to_owned()).into_py(py);
+        py_assert!(py, obj, "obj == 'test'");
+
+        let obj = TransparentStruct { inner: 0.5 }.into_py(py);
+        py_assert!(py, obj, "obj == 0.5");
+        let roundtrip: TransparentStruct = obj.extract(py).unwrap();
+        assert_eq!(roundtrip, TransparentStruct { inner: 0.5 });
+    });
+}
+
+#[derive(Debug, PartialEq, FromPyObject, IntoPyObject, ToPyObject)]
+pub struct Generic<T> {
+    #[pyo3(item)]
+    value: T,
+}
+
+#[test]
+fn test_generic() {
+    Python::with_gil(|py| {
+        let obj = Generic { value: "foo" }.to_object(py);
+        py_assert!(py, obj, "obj == {'value': 'foo'}");
+        let roundtrip: Generic<String> = obj.extract(py).unwrap();
+        assert_eq!(roundtrip.value, "foo");
+    });
+}
+
+#[derive(Debug, Clone, PartialEq, FromPyObject, IntoPyObject, ToPyObject)]
+pub enum Foo {
+    TupleVar(usize, String),
+    StructVar {
+        #[pyo3(item)]
+        test: String,
+    },
+    #[pyo3(transparent)]
+    TransparentTuple(usize),
+    #[pyo3(transparent)]
+    TransparentStructVar {
+        a: Option<String>,
+    },
+}
+
+#[test]
+fn test_enum() {
+    Python::with_gil(|py| {
+        let values = vec![
+            (Foo::TupleVar(1, "test".to_owned()), "(1, 'test')"),
+            (
+                Foo::StructVar {
+                    test: "foo".to_owned(),
+                },
+                "{'test': 'foo'}",
+            ),
+            (Foo::TransparentTuple(2), "2"),
+            (Foo::TransparentStructVar { a: None }, "None"),
+        ];
+        for (value, expected) in values {
+            let obj = value.to_object(py);
+            assert_eq!(obj.as_ref(py).repr().unwrap().to_str().unwrap(), expected);
+            let roundtrip: Foo = value.clone().into_py(py).extract(py).unwrap();
+            assert_eq!(roundtrip, value);
+        }
+    });
+}
+
+#[pyfunction]
+fn make_point(x: i32, y: i32) -> Point {
+    Point { x, y }
+}
+
+#[test]
+fn test_return_from_pyfunction() {
+    Python::with_gil(|py| {
+        let f = wrap_pyfunction!(make_point)(py).unwrap();
+        py_assert!(py, f, "f(3, 4) == {'x': 3, 'y_coord': 4}");
+    });
+}
+
+#[derive(Debug, PartialEq, FromPyObject, IntoPyObject)]
+#[pyo3(from_item_all, rename_all = "camelCase")]
+pub struct Config {
+    server_name: String,
+    #[pyo3(item("PORT"))]
+    listen_port: u16,
+    #[pyo3(default)]
+    retry_count: Option<u32>,
+}
+
+#[test]
+fn test_renamed_roundtrip() {
+    Python::with_gil(|py| {
+        let config = Config {
+            server_name: "a".to_owned(),
+            listen_port: 80,
+            retry_count: None,
+        };
+        let obj = config.into_py(py);
+        py_assert!(
+            py,
+            obj,
+            "obj == {'serverName': 'a', 'PORT': 80, 'retryCount': None}"
+        );
+        let roundtrip: Config = obj.extract(py).unwrap();
+        assert_eq!(roundtrip.server_name, "a");
+        assert_eq!(roundtrip.listen_port, 80);
+        assert_eq!(roundtrip.retry_count, None);
+    });
+}
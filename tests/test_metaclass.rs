@@ -0,0 +1,234 @@
+#![cfg(not(Py_LIMITED_API))]
+
+use pyo3::class::PyMappingProtocol;
+use pyo3::exceptions::PyKeyError;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyType};
+use pyo3::{py_run, AsPyPointer};
+use std::collections::HashMap;
+
+mod common;
+
+/// A metaclass which keeps a registry per class, looked up with `cls[key]`.
+#[pyclass(extends=PyType, subclass)]
+#[derive(Default)]
+struct RegistryMeta {
+    entries: HashMap<String, PyObject>,
+}
+
+#[pymethods]
+impl RegistryMeta {
+    // Called for classes created from Python, like `class Sub(Codec): ...`
+    #[new]
+    fn new(py: Python, name: &PyAny, bases: &PyAny, dict: &PyAny) -> PyClassInitializer<Self> {
+        PyClassInitializer::from(Self::default()).with_base_args(py, (name, bases, dict))
+    }
+
+    fn register(&mut self, key: String, value: PyObject) {
+        self.entries.insert(key, value);
+    }
+
+    #[getter]
+    fn registered(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.entries.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+}
+
+#[pyproto]
+impl PyMappingProtocol for RegistryMeta {
+    fn __len__(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn __getitem__(&self, key: String) -> PyResult<PyObject> {
+        self.entries
+            .get(&key)
+            .cloned()
+            .ok_or_else(|| PyKeyError::new_err(key))
+    }
+}
+
+/// Encodes and decodes things.
+#[pyclass(metaclass=RegistryMeta, subclass)]
+struct Codec {
+    #[pyo3(get)]
+    name: String,
+}
+
+#[pymethods]
+impl Codec {
+    #[new]
+    fn new(name: String) -> Self {
+        Codec { name }
+    }
+
+    #[classattr]
+    const VERSION: u32 = 2;
+
+    fn describe(&self) -> String {
+        format!("codec {}", self.name)
+    }
+}
+
+// Protocol slots are inherited from the type object created from the spec of the class
+#[pyproto]
+impl PyMappingProtocol for Codec {
+    fn __len__(&self) -> usize {
+        self.name.len()
+    }
+}
+
+#[pyclass(metaclass=RegistryMeta)]
+struct Format {}
+
+#[test]
+fn class_is_instance_of_metaclass() {
+    Python::with_gil(|py| {
+        let codec = py.get_type::<Codec>();
+        let meta = py.get_type::<RegistryMeta>();
+        py_run!(
+            py,
+            codec meta,
+            r#"
+assert type(codec) is meta
+assert isinstance(codec, type)
+assert codec.__name__ == "Codec"
+assert codec.__module__ == "builtins"
+assert codec.__doc__ == "Encodes and decodes things."
+# the class derives from the type object created from the class's spec
+slots = codec.__mro__[1]
+assert codec.__mro__ == (codec, slots, object)
+assert type(slots) is type and slots.__name__ == "Codec"
+assert codec.VERSION == 2
+
+c = codec("utf-8")
+assert isinstance(c, codec)
+assert c.name == "utf-8"
+assert c.describe() == "codec utf-8"
+assert len(c) == 5
+assert not hasattr(c, "register")
+assert not hasattr(c, "__dict__")
+"#
+        );
+        assert_eq!(codec.get_type().as_ptr(), meta.as_ptr());
+        assert!(codec
+            .is_instance(codec.call1(("latin-1",)).unwrap())
+            .unwrap());
+    });
+}
+
+#[test]
+fn metaclass_data_per_class() {
+    Python::with_gil(|py| {
+        let codec = py.get_type::<Codec>();
+        let format = py.get_type::<Format>();
+        py_run!(
+            py,
+            codec format,
+            r#"
+assert len(codec) == 0
+codec.register("utf-8", "UTF8")
+assert codec["utf-8"] == "UTF8"
+assert codec.registered == ["utf-8"]
+try:
+    codec["ascii"]
+except KeyError:
+    pass
+else:
+    assert False
+
+# each class object has its own data
+assert len(format) == 0
+format.register("json", 1)
+assert codec.registered == ["utf-8"]
+
+# like the class, its metaclass instance is not subclassable without `subclass`
+try:
+    class Sub(format):
+        pass
+except TypeError:
+    pass
+else:
+    assert False
+"#
+        );
+        let cell: &PyCell<RegistryMeta> = codec.downcast().unwrap();
+        assert!(cell.borrow().entries.contains_key("utf-8"));
+    });
+}
+
+#[pyclass(extends=Codec)]
+struct TextCodec {}
+
+#[test]
+fn subclass_inherits_metaclass() {
+    Python::with_gil(|py| {
+        let codec = py.get_type::<Codec>();
+        let text_codec = py.get_type::<TextCodec>();
+        let meta = py.get_type::<RegistryMeta>();
+        py_run!(
+            py,
+            codec text_codec meta,
+            r#"
+assert type(text_codec) is meta
+assert text_codec.__mro__[2:] == codec.__mro__
+text_codec.register("utf-16", 16)
+assert "utf-16" not in codec.registered
+"#
+        );
+    });
+}
+
+#[test]
+fn python_subclass_uses_metaclass() {
+    Python::with_gil(|py| {
+        let codec = py.get_type::<Codec>();
+        let meta = py.get_type::<RegistryMeta>();
+        py_run!(
+            py,
+            codec meta,
+            r#"
+import gc, weakref
+
+class Sub(codec):
+    pass
+
+assert type(Sub) is meta
+assert len(Sub) == 0
+assert Sub("rot13").describe() == "codec rot13"
+
+class Other(metaclass=meta):
+    pass
+
+class Value:
+    pass
+
+value = Value()
+ref = weakref.ref(value)
+Other.register("value", value)
+del value
+assert ref() is not None
+
+# the registry is dropped with the class
+del Other
+gc.collect()
+assert ref() is None
+"#
+        );
+    });
+}
+
+#[pyclass(extends=Codec, metaclass=PyType)]
+struct Conflicting {}
+
+#[test]
+#[should_panic(expected = "An error occurred while initializing class Conflicting")]
+fn metaclass_conflict() {
+    Python::with_gil(|py| {
+        let ty = py.get_type::<Conflicting>();
+        let d = [("ty", ty)].into_py_dict(py);
+        py.run("ty", None, Some(d)).unwrap();
+    });
+}
@@ -113,6 +113,126 @@ fn class_method_with_args() {
     );
 }
 
+#[pyclass(subclass)]
+struct PluginBase {}
+
+#[pymethods]
+impl PluginBase {
+    #[classattr]
+    fn registry() -> Vec<PyObject> {
+        Vec::new()
+    }
+
+    // Implicitly a class method, like in Python
+    #[args(kwargs = "**")]
+    fn __init_subclass__(cls: &PyType, kwargs: Option<&PyDict>) -> PyResult<()> {
+        let name = match kwargs.and_then(|kwargs| kwargs.get_item("name")) {
+            Some(name) => name.extract()?,
+            None => cls.name()?.to_lowercase(),
+        };
+        cls.setattr("plugin_name", name)?;
+        cls.getattr("registry")?.call_method1("append", (cls,))?;
+        Ok(())
+    }
+}
+
+#[test]
+fn init_subclass() {
+    Python::with_gil(|py| {
+        let base = py.get_type::<PluginBase>();
+        py_run!(
+            py,
+            base,
+            r#"
+class Json(base):
+    pass
+
+class Yaml(base, name="yml"):
+    pass
+
+assert Json.plugin_name == "json"
+assert Yaml.plugin_name == "yml"
+assert base.registry == [Json, Yaml]
+assert not hasattr(base, "plugin_name")
+"#
+        );
+    });
+}
+
+#[pyclass(subclass)]
+struct GenericContainer {}
+
+#[pymethods]
+impl GenericContainer {
+    fn __class_getitem__(cls: &PyType, item: &PyAny) -> PyResult<String> {
+        Ok(format!("{}[{}]", cls.name()?, item.getattr("__name__")?))
+    }
+}
+
+#[test]
+#[cfg(Py_3_7)]
+fn class_getitem() {
+    Python::with_gil(|py| {
+        let container = py.get_type::<GenericContainer>();
+        py_assert!(py, container, "container[int] == 'GenericContainer[int]'");
+        py_run!(
+            py,
+            container,
+            r#"
+class Sub(container):
+    pass
+
+assert Sub[str] == "Sub[str]"
+"#
+        );
+    });
+}
+
+#[pyclass]
+struct Field {
+    #[pyo3(get)]
+    name: Option<String>,
+    #[pyo3(get)]
+    owner: Option<String>,
+}
+
+#[pymethods]
+impl Field {
+    #[new]
+    fn new() -> Self {
+        Field {
+            name: None,
+            owner: None,
+        }
+    }
+
+    fn __set_name__(&mut self, owner: &PyType, name: String) -> PyResult<()> {
+        self.owner = Some(owner.name()?.to_string());
+        self.name = Some(name);
+        Ok(())
+    }
+}
+
+#[test]
+fn set_name() {
+    Python::with_gil(|py| {
+        // class bodies only see globals
+        let globals = [("field", py.get_type::<Field>())].into_py_dict(py);
+        py.run(
+            r#"
+class Model:
+    id = field()
+
+assert Model.id.name == "id"
+assert Model.id.owner == "Model"
+"#,
+            Some(globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
 #[pyclass]
 struct StaticMethod {}
 
@@ -213,3 +213,33 @@ fn test_conversion_error() {
         "argument 'option_arg': 'str' object cannot be interpreted as an integer"
     );
 }
+
+#[pyfunction]
+fn wrapper_types(
+    name: Box<str>,
+    data: std::sync::Arc<[u8]>,
+    count: std::num::NonZeroU32,
+    total: std::num::Wrapping<u64>,
+) -> (std::rc::Rc<str>, usize, u32, u64) {
+    (name.into(), data.len(), count.get(), total.0)
+}
+
+#[test]
+fn test_wrapper_types() {
+    let gil = Python::acquire_gil();
+    let py = gil.python();
+
+    let wrapper_types = wrap_pyfunction!(wrapper_types)(py).unwrap();
+    py_assert!(
+        py,
+        wrapper_types,
+        "wrapper_types('name', b'data', 3, 2**64 - 1) == ('name', 4, 3, 2**64 - 1)"
+    );
+    py_expect_exception!(
+        py,
+        wrapper_types,
+        "wrapper_types('name', b'data', 0, 0)",
+        PyValueError,
+        "invalid zero value"
+    );
+}
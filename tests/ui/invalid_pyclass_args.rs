@@ -21,4 +21,28 @@ struct InvalidModule {}
 #[pyclass(weakrev)]
 struct InvalidArg {}
 
+#[pyclass(ord)]
+struct OrdWithoutEq {}
+
+#[pyclass(hash)]
+struct HashWithoutEq {}
+
+#[pyclass(get_all)]
+struct UselessGet {
+    #[pyo3(get)]
+    value: i32,
+}
+
+#[pyclass(set_all)]
+struct UselessSet {
+    #[pyo3(set)]
+    value: i32,
+}
+
+#[pyclass(pickle)]
+struct PickleWithoutModule {}
+
+#[pyclass(pickle, module = "my_module", extends = pyo3::types::PyDict)]
+struct PickleWithExtends {}
+
 fn main() {}